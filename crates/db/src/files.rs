@@ -72,3 +72,21 @@ pub enum DataType {
     ItemTags,
     Nbtdoc,
 }
+
+impl DataType {
+    /// Every type of data, in order.
+    pub const ALL: [DataType; 12] = [
+        DataType::Advancements,
+        DataType::LootTables,
+        DataType::Functions,
+        DataType::Predicates,
+        DataType::Recipes,
+        DataType::Structures,
+        DataType::BlockTags,
+        DataType::EntityTypeTags,
+        DataType::FluidTags,
+        DataType::FunctionTags,
+        DataType::ItemTags,
+        DataType::Nbtdoc,
+    ];
+}
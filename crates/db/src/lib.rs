@@ -4,7 +4,10 @@ mod files;
 pub use files::{DataType, Datapack, DatapackId, FileId, NamespaceId};
 use relative_path::RelativePath;
 use relative_path::RelativePathBuf;
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 #[salsa::query_group(FsDatabaseStorage)]
 pub trait FsDatabase: std::fmt::Debug {
@@ -20,6 +23,16 @@ pub trait FsDatabase: std::fmt::Debug {
     #[salsa::input]
     fn datapack(&self, id: DatapackId) -> Arc<Datapack>;
 
+    /// The names of the namespaces in every datapack, which are the only names with a `namespace_id`.
+    ///
+    /// This has to be set before anything looks up a namespace by name,
+    /// to an empty set if there are no datapacks. Use [`set_namespaces`] to set it
+    /// together with the inputs of every namespace.
+    ///
+    /// [`set_namespaces`]: ./fn.set_namespaces.html
+    #[salsa::input]
+    fn namespaces(&self) -> Arc<HashSet<String>>;
+
     #[salsa::input]
     fn namespace_id(&self, name: String) -> NamespaceId;
 
@@ -34,3 +47,31 @@ pub trait FsDatabase: std::fmt::Debug {
     #[salsa::input]
     fn namespace_info(&self, id: NamespaceId, data: DataType) -> Arc<HashSet<RelativePathBuf>>;
 }
+
+/// Sets `namespaces`, `namespace_id` and `namespace_info` for all of the namespaces in `namespaces`.
+///
+/// Every namespace gets a `namespace_info` for each [`DataType`],
+/// which is empty for the types that are missing from its map.
+///
+/// [`DataType`]: ./enum.DataType.html
+pub fn set_namespaces<I>(db: &mut dyn FsDatabase, namespaces: I)
+where
+    I: IntoIterator<
+        Item = (
+            String,
+            NamespaceId,
+            HashMap<DataType, HashSet<RelativePathBuf>>,
+        ),
+    >,
+{
+    let mut names = HashSet::new();
+    for (name, id, mut data) in namespaces {
+        db.set_namespace_id(name.clone(), id);
+        for ty in DataType::ALL.iter() {
+            let files = data.remove(ty).unwrap_or_default();
+            db.set_namespace_info(id, *ty, Arc::new(files));
+        }
+        names.insert(name);
+    }
+    db.set_namespaces(Arc::new(names));
+}
@@ -1,310 +1,293 @@
 use super::{McGroupType, McTokenKind, McfLang};
-use mcfunction_parse::ast::{Ast, AstView, CstNode, OwnedNode, SyntaxKind};
+use mcfunction_parse::ast::{AstView, CstNode, SyntaxKind};
 use std::sync::Arc;
-use util::commands::{CommandNodeType, Commands, Index, ParserType};
+use util::{
+    commands::{CommandNodeType, Commands, Index, ParserType},
+    Identifier,
+};
 
-pub type Node = OwnedNode<Arc<Ast<Arc<str>, McfLang>>>;
-pub type RefNode<'a> = OwnedNode<DerefRef<'a, Arc<Ast<Arc<str>, McfLang>>>>;
+pub trait CommandNodeItem: CstNode<String = Arc<str>, Language = McfLang> {
+    fn valid_type(pt: ParserType) -> bool;
+    fn is_literal() -> bool;
+}
+
+macro_rules! impl_cni {
+    ($id:ident: $($ty:ident),+) => {
+        impl<N: NH> CommandNodeItem for $id<N> {
+            fn valid_type(pt: ParserType) -> bool {
+                match pt {
+                    $(ParserType::$ty { .. })|+ => true,
+                    _ => false,
+                }
+            }
 
-pub trait NH {
-    fn into_arc(self) -> Node;
-    fn as_ref(&self) -> RefNode;
-    fn view(&self) -> AstView<Arc<str>, McfLang>;
+            fn is_literal() -> bool {
+                false
+            }
+        }
+    };
 }
-impl NH for Node {
-    fn into_arc(self) -> Node {
-        self
-    }
 
-    fn as_ref(&self) -> RefNode {
-        self.borrow(|v| DerefRef(v))
+mcfunction_parse::cst! {
+    lang = McfLang, group = McGroupType, token = McTokenKind;
+
+    pub struct File: root File {
+        pub fn lines: children Line;
     }
 
-    fn view(&self) -> AstView<Arc<str>, McfLang> {
-        self.view()
+    pub enum Line {
+        Command = group Command => command: Command,
+        Comment = joined Comment => comment: Comment,
     }
-}
-impl<'a> NH for RefNode<'a> {
-    fn into_arc(self) -> Node {
-        self.convert(|d| d.0.clone())
+
+    pub struct Command: group Command {
+        pub fn nodes: children CommandNode;
     }
 
-    fn as_ref(&self) -> RefNode {
-        self.borrow(|d| *d)
+    pub struct Comment: joined Comment;
+
+    pub enum NbtValue {
+        Compound = group NbtCompound => compound: NbtCompound,
+        Sequence = group NbtSequence => sequence: NbtSequence,
+        Number = group NbtNumber => number: NbtNumber,
+        String = group NbtString => string: NbtString,
+        Boolean = joined NbtBoolean => boolean: NbtBoolean,
     }
 
-    fn view(&self) -> AstView<Arc<str>, McfLang> {
-        self.view()
+    pub struct NbtCompound: group NbtCompound {
+        pub fn entries: children NbtCompoundEntry;
     }
-}
 
-pub trait CommandNodeItem: CstNode<String = Arc<str>, Language = McfLang> {
-    fn valid_type(pt: ParserType) -> bool;
-    fn is_literal() -> bool;
-}
+    pub struct NbtCompoundEntry: group NbtCompoundEntry {
+        pub fn key: first McfString;
+        pub fn value: first NbtValue;
+    }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct DerefRef<'a, D>(&'a D);
-impl<'a, D> std::ops::Deref for DerefRef<'a, D>
-where
-    D: std::ops::Deref,
-{
-    type Target = D::Target;
-    fn deref(&self) -> &Self::Target {
-        self.0.deref()
+    pub struct NbtSequence: group NbtSequence {
+        pub fn entries: children NbtValue;
     }
-}
-impl<'a, T> Clone for DerefRef<'a, T> {
-    fn clone(&self) -> Self {
-        *self
+
+    // INTERNAL USE
+    struct Semicolon: token Semicolon;
+    struct NbtPrefixB: joined NbtPrefixB;
+    struct NbtPrefixI: joined NbtPrefixI;
+    struct NbtPrefixL: joined NbtPrefixL;
+    struct NbtSuffixB: joined NbtSuffixB;
+    struct NbtSuffixS: joined NbtSuffixS;
+    struct NbtSuffixL: joined NbtSuffixL;
+    struct NbtSuffixF: joined NbtSuffixF;
+    struct NbtSuffixD: joined NbtSuffixD;
+
+    pub struct NbtNumber: group NbtNumber;
+    pub struct NbtString: group NbtString;
+    pub struct NbtBoolean: joined NbtBoolean;
+    pub struct IntRangeArgument: group Range;
+
+    pub enum McfString {
+        Quoted = token QuotedString,
+        Unquoted = joined UnquotedString,
     }
-}
-impl<'a, T> Copy for DerefRef<'a, T> {}
-
-macro_rules! impl_cst {
-    ($id:ident, group $ty:ident) => {
-        impl<N: NH> CstNode for $id<N> {
-            type String = Arc<str>;
-            type Language = McfLang;
-            type Node = N;
-
-            fn can_cast(value: AstView<Arc<str>, McfLang>) -> bool {
-                match value.kind() {
-                    SyntaxKind::Group(McGroupType::$ty) => true,
-                    _ => false,
-                }
-            }
 
-            fn view(&self) -> AstView<Arc<str>, McfLang> {
-                self.0.view()
-            }
+    pub struct FloatToken: joined Float;
+    pub struct Integer: joined Integer;
+    pub struct UnquotedString: joined UnquotedString;
+    pub struct ResourceLocation: joined ResourceLocation;
+    pub struct Uuid: joined Uuid;
+
+    /// A literal in a command, such as `give`
+    pub struct Literal: token Word;
+    pub struct Color: joined Color;
+    /// A set of axes, such as `xz`
+    pub struct Swizzle: joined Swizzle;
+    pub struct EntityAnchor: joined EntityAnchor;
+    /// A scoreboard display slot, such as `sidebar.team.red`
+    pub struct ScoreboardSlot: joined ScoreboardSlot;
+    /// The rest of the line, such as the text of `say`
+    pub struct Message: group UnquotedString;
+
+    pub enum Boolean {
+        True = joined BooleanTrue,
+        False = joined BooleanFalse,
+    }
 
-            fn into_node(self) -> Self::Node {
-                self.0
-            }
+    pub struct Operation: joined Operation {
+        pub fn operator: first Operator;
+    }
 
-            fn new(node: N) -> Self {
-                Self(node)
-            }
-        }
+    pub enum Operator {
+        Assign = token Eq,
+        AddAssign = token AddAssign,
+        SubAssign = token SubAssign,
+        MulAssign = token MulAssign,
+        DivAssign = token DivAssign,
+        ModAssign = token ModAssign,
+        Swap = token Swap,
+        Min = token Lt,
+        Max = token Gt,
+    }
 
-        impl<N: NH> $id<N> {
-            pub fn into_arc(self) -> $id<Node> {
-                $id(self.0.into_arc())
-            }
+    pub struct Time: group Time;
 
-            pub fn as_ref(&self) -> $id<RefNode> {
-                $id(self.0.as_ref())
-            }
-        }
-    };
-    ($id:ident, joined $ty:ident) => {
-        impl<N: NH> CstNode for $id<N> {
-            type String = Arc<str>;
-            type Language = McfLang;
-            type Node = N;
-
-            fn can_cast(value: AstView<Arc<str>, McfLang>) -> bool {
-                match value.kind() {
-                    SyntaxKind::Joined(McGroupType::$ty) => true,
-                    _ => false,
-                }
-            }
+    pub struct Coord: group Coord {
+        pub fn parts: children CoordPart;
+    }
 
-            fn view(&self) -> AstView<Arc<str>, McfLang> {
-                self.0.view()
-            }
+    pub struct CoordPart: group CoordPart {
+        pub fn offset: first FloatToken;
+    }
 
-            fn into_node(self) -> Self::Node {
-                self.0
-            }
+    // INTERNAL USE
+    struct Tilde: token Tilde;
+    struct Caret: token Caret;
+    struct Hash: token Hash;
+    struct Excl: token Excl;
+    struct Eq: token Eq;
+    struct Colon: token Colon;
+    struct DotDot: token DotDot;
+
+    pub struct Selector: group Selector {
+        pub fn name: first UnquotedString;
+        pub fn uuid: first Uuid;
+    }
 
-            fn new(node: N) -> Self {
-                Self(node)
-            }
-        }
+    // INTERNAL USE
+    struct SelectorArgument: group SelectorArgument {
+        fn entries: children SelectorArgumentEntry;
+    }
 
-        #[allow(dead_code)]
-        impl<N: NH> $id<N> {
-            pub fn string(&self) -> &str {
-                self.0.view().string()
-            }
+    pub struct SelectorArgumentEntry: group SelectorArgumentEntry {
+        pub fn key: first UnquotedString;
+    }
 
-            pub fn into_arc(self) -> $id<Node> {
-                $id(self.0.into_arc())
-            }
+    pub struct SelectorArgumentMap: group SelectorArgumentMap {
+        pub fn entries: children SelectorArgumentMapEntry;
+    }
 
-            pub fn as_ref(&self) -> $id<RefNode> {
-                $id(self.0.as_ref())
-            }
-        }
-    };
-    ($id:ident, token $ty:ident) => {
-        impl<N: NH> CstNode for $id<N> {
-            type String = Arc<str>;
-            type Language = McfLang;
-            type Node = N;
-
-            fn can_cast(value: AstView<Arc<str>, McfLang>) -> bool {
-                match value.kind() {
-                    SyntaxKind::Token(McTokenKind::$ty) => true,
-                    _ => false,
-                }
-            }
+    pub struct SelectorArgumentMapEntry: group SelectorArgumentMapEntry;
+
+    pub enum SelectorValue {
+        String = token QuotedString,
+        Unquoted = joined UnquotedString => unquoted: UnquotedString,
+        Float = joined Float => float: FloatToken,
+        Integer = joined Integer => integer: Integer,
+        Map = group SelectorArgumentMap => map: SelectorArgumentMap,
+        Range = group Range => range: IntRangeArgument,
+        ResourceLocation = joined ResourceLocation => resource_location: ResourceLocation,
+        Nbt = group NbtCompound => nbt: NbtCompound,
+        Sort = joined SelectorSort,
+        Gamemode = joined SelectorGamemode,
+        True = joined BooleanTrue,
+        False = joined BooleanFalse,
+    }
 
-            fn view(&self) -> AstView<Arc<str>, McfLang> {
-                self.0.view()
-            }
+    pub struct BlockState: group BlockState {
+        pub fn id: first ResourceLocation;
+        pub fn nbt: first NbtCompound;
+    }
 
-            fn into_node(self) -> Self::Node {
-                self.0
-            }
+    // INTERNAL USE
+    struct BlockStateArguments: group BlockStateArguments {
+        fn properties: children BlockStateProperty;
+    }
 
-            fn new(node: N) -> Self {
-                Self(node)
-            }
-        }
+    pub struct BlockStateProperty: group BlockStateProperty {
+        pub fn key: first UnquotedString;
+    }
 
-        impl<N: NH> $id<N> {
-            #[allow(dead_code)]
-            pub fn into_arc(self) -> $id<Node> {
-                $id(self.0.into_arc())
-            }
+    pub struct ItemStack: group ItemStack {
+        pub fn id: first ResourceLocation;
+        pub fn nbt: first NbtCompound;
+    }
 
-            #[allow(dead_code)]
-            pub fn as_ref(&self) -> $id<RefNode> {
-                $id(self.0.as_ref())
-            }
-        }
-    };
-    (enum $id:ident, $( $nid:ident $hty:ident $xid:ident $($gfn:ident $ret:ident)? ),+ ) => {
-        impl<N: NH> CstNode for $id<N> {
-            type String = Arc<str>;
-            type Language = McfLang;
-            type Node = N;
-
-            fn can_cast(value: AstView<Arc<str>, McfLang>) -> bool {
-                match value.kind() {
-                    $(cst_macro_helper!($hty $xid) => true),+,
-                    _ => false
-                }
-            }
+    pub struct ItemPredicate: group ItemPredicate {
+        pub fn id: first ResourceLocation;
+        pub fn nbt: first NbtCompound;
+    }
 
-            fn view(&self) -> AstView<Arc<str>, McfLang> {
-                match self {
-                    $(Self::$nid(v) => v.view()),*
-                }
-            }
+    pub struct Function: group Function {
+        pub fn id: first ResourceLocation;
+    }
 
-            fn into_node(self) -> Self::Node {
-                match self {
-                    $(Self::$nid(v) => v),*
-                }
-            }
+    pub struct NbtPath: group NbtPath {
+        pub fn segments: children NbtPathSegment;
+    }
 
-            fn new(node: N) -> Self {
-                match node.view().kind() {
-                    $(cst_macro_helper!($hty $xid) => Self::$nid(node)),*,
-                    _ => panic!("Invalid node type")
-                }
-            }
-        }
+    pub struct NbtPathSegment: group NbtPathSegment {
+        pub fn index: first NbtPathIndex;
+    }
 
-        impl<N: NH> $id<N> {
-            $( $(
-                pub fn $gfn(self) -> Option<$ret<N>> {
-                    match self {
-                        Self::$nid(v) => if $ret::<N>::can_cast(v.view()) {
-                            Some($ret::new(v))
-                        } else {
-                            None
-                        },
-                        _ => None
-                    }
-                }
-            )? )+
+    pub struct NbtPathIndex: group NbtPathIndex {
+        pub fn position: first Integer;
+        pub fn filter: first NbtCompound;
+    }
 
-            #[allow(dead_code)]
-            pub fn into_arc(self) -> $id<Node> {
-                $id::<Node>::new(self.into_node().into_arc())
-            }
+    // INTERNAL USE
+    enum NbtPathName {
+        Word = token Word,
+        Quoted = token QuotedString,
+    }
 
-            #[allow(dead_code)]
-            pub fn as_ref(&self) -> $id<RefNode> {
-                $id::<RefNode>::new(match self {
-                    $( Self::$nid(v) => v.as_ref() ),*
-                })
-            }
-        }
+    pub enum JsonValue {
+        Object = group JsonObject => object: JsonObject,
+        Array = group JsonList => array: JsonArray,
+        String = token QuotedString,
+        True = joined BooleanTrue,
+        False = joined BooleanFalse,
+        Null = joined JsonNull,
+        Number = joined Float => number: FloatToken,
     }
-}
-macro_rules! cst_macro_helper {
-    (token $id:ident) => {
-        SyntaxKind::Token(McTokenKind::$id)
-    };
-    (group $id:ident) => {
-        SyntaxKind::Group(McGroupType::$id)
-    };
-    (joined $id:ident) => {
-        SyntaxKind::Joined(McGroupType::$id)
-    };
-}
-macro_rules! impl_cni {
-    ($id:ident $ty:ident {}) => {
-        impl<N: NH> CommandNodeItem for $id<N> {
-            fn valid_type(pt: ParserType) -> bool {
-                match pt {
-                    ParserType::$ty { .. } => true,
-                    _ => false,
-                }
-            }
 
-            fn is_literal() -> bool {
-                false
-            }
-        }
-    };
-    ($id:ident $ty:ident) => {
-        impl<N: NH> CommandNodeItem for $id<N> {
-            fn valid_type(pt: ParserType) -> bool {
-                match pt {
-                    ParserType::$ty => true,
-                    _ => false,
-                }
-            }
+    pub struct JsonObject: group JsonObject {
+        pub fn entries: children JsonObjectEntry;
+    }
 
-            fn is_literal() -> bool {
-                false
-            }
-        }
-    };
-}
+    pub struct JsonObjectEntry: group JsonObjectEntry;
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct File<N>(N);
-impl_cst!(File, group File);
-impl<N: NH> File<N> {
-    pub fn lines(&self) -> impl Iterator<Item = Line<RefNode>> {
-        self.0.as_ref().children()
+    pub struct JsonArray: group JsonList {
+        pub fn values: children JsonValue;
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum Line<N> {
-    Command(N),
-    Comment(N),
-}
-impl_cst!(enum Line,
-    Command group Command command Command,
-    Comment group Comment comment Comment
+impl_cni!(NbtValue: NbtTag);
+impl_cni!(NbtCompound: NbtCompoundTag);
+impl_cni!(NbtPath: NbtPath);
+impl_cni!(IntRangeArgument: IntRange);
+impl_cni!(FloatToken: Double, Float);
+impl_cni!(Integer: Integer);
+impl_cni!(UnquotedString: ItemSlot, Objective, Team, String);
+impl_cni!(McfString: String);
+impl_cni!(Message: Message, String);
+impl_cni!(
+    ResourceLocation: Dimension,
+    EntitySummon,
+    ItemEnchantment,
+    MobEffect,
+    Particle,
+    ResourceLocation,
+    ObjectiveCriteria
 );
+impl_cni!(Color: Color);
+impl_cni!(Swizzle: Swizzle);
+impl_cni!(EntityAnchor: EntityAnchor);
+impl_cni!(ScoreboardSlot: ScoreboardSlot);
+impl_cni!(Boolean: Bool);
+impl_cni!(Operation: Operation);
+impl_cni!(Time: Time);
+impl_cni!(Coord: BlockPos, ColumnPos, Rotation, Vec2, Vec3);
+impl_cni!(Selector: Entity, GameProfile, ScoreHolder);
+impl_cni!(BlockState: BlockPredicate, BlockState);
+impl_cni!(ItemStack: ItemStack);
+impl_cni!(ItemPredicate: ItemPredicate);
+impl_cni!(Function: Function);
+impl_cni!(JsonValue: Component);
+
+impl<N: NH> CommandNodeItem for Literal<N> {
+    fn valid_type(_: ParserType) -> bool {
+        false
+    }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Command<N>(N);
-impl_cst!(Command, group Command);
-impl<N: NH> Command<N> {
-    pub fn nodes(&self) -> impl Iterator<Item = CommandNode<RefNode>> {
-        self.0.as_ref().children()
+    fn is_literal() -> bool {
+        true
     }
 }
 
@@ -363,53 +346,40 @@ impl<N: NH> CommandNode<N> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Comment<N>(N);
-impl_cst!(Comment, group Comment);
-
-#[derive(Debug, PartialEq, Eq)]
-pub enum NbtValue<N> {
-    Compound(N),
-    Sequence(N),
-    Number(N),
-    String(N),
-    Boolean(N),
-}
-impl_cst!(enum NbtValue,
-    Compound group NbtCompound compound NbtCompound,
-    Sequence group NbtSequence sequence NbtSequence,
-    Number group NbtNumber number NbtNumber,
-    String group NbtString string NbtString,
-    Boolean group NbtBoolean boolean NbtBoolean
-);
-impl_cni!(NbtValue NbtTag);
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct NbtCompound<N>(N);
-impl_cst!(NbtCompound, group NbtCompound);
-impl_cni!(NbtCompound NbtCompoundTag);
-impl<N: NH> NbtCompound<N> {
-    pub fn entries(&self) -> impl Iterator<Item = NbtCompoundEntry<RefNode>> {
-        self.0.as_ref().children()
-    }
-}
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct NbtCompoundEntry<N>(N);
-impl_cst!(NbtCompoundEntry, group NbtCompoundEntry);
-impl<N: NH> NbtCompoundEntry<N> {
-    pub fn key(&self) -> Option<McfString<RefNode>> {
-        self.0.as_ref().first_child()
-    }
-
-    pub fn value(&self) -> Option<NbtValue<RefNode>> {
-        self.0.as_ref().first_child()
+impl<N: NH> Command<N> {
+    /// Gets the redirects in the command that loop back to a node that came earlier in it,
+    /// as the node that redirects and the node that it leads to.
+    ///
+    /// For example, `execute as @a at @s` loops back from `targets` to `execute`.
+    /// A redirect only counts once the command continues after it.
+    pub fn loop_backs(&self, cmds: &Commands) -> Vec<(CommandNode<RefNode<'_>>, Index)> {
+        let mut out = vec![];
+        let mut path: Vec<Index> = vec![];
+        let mut nodes = self.nodes().peekable();
+        while let Some(node) = nodes.next() {
+            path.push(node.index());
+            if nodes.peek().is_none() {
+                break;
+            }
+            let mut redirects = vec![node.index()];
+            let mut loops_back = None;
+            while let Some(target) = cmds[*redirects.last().unwrap()].redirect() {
+                if redirects.contains(&target) {
+                    break;
+                }
+                redirects.push(target);
+                if path.contains(&target) {
+                    loops_back = Some(target);
+                }
+            }
+            if let Some(target) = loops_back {
+                out.push((node, target));
+            }
+        }
+        out
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct NbtSequence<N>(N);
-impl_cst!(NbtSequence, group NbtSequence);
 impl<N: NH> NbtSequence<N> {
     pub fn seq_type(&self) -> NbtSequenceType {
         let semi = self.0.as_ref().first_child::<Semicolon<RefNode>>();
@@ -443,10 +413,6 @@ impl<N: NH> NbtSequence<N> {
             None => NbtSequenceType::List,
         }
     }
-
-    pub fn entries(&self) -> impl Iterator<Item = NbtValue<RefNode>> {
-        self.0.as_ref().children()
-    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -458,45 +424,6 @@ pub enum NbtSequenceType {
     ErrorArray,
 }
 
-// INTERNAL USE
-struct Semicolon<N>(N);
-impl_cst!(Semicolon, token Semicolon);
-
-// INTERNAL USE
-struct NbtPrefixB<N>(N);
-impl_cst!(NbtPrefixB, joined NbtPrefixB);
-
-// INTERNAL USE
-struct NbtPrefixI<N>(N);
-impl_cst!(NbtPrefixI, joined NbtPrefixI);
-
-// INTERNAL USE
-struct NbtPrefixL<N>(N);
-impl_cst!(NbtPrefixL, joined NbtPrefixL);
-
-// INTERNAL USE
-struct NbtSuffixB<N>(N);
-impl_cst!(NbtSuffixB, joined NbtSuffixB);
-
-// INTERNAL USE
-struct NbtSuffixS<N>(N);
-impl_cst!(NbtSuffixS, joined NbtSuffixS);
-
-// INTERNAL USE
-struct NbtSuffixL<N>(N);
-impl_cst!(NbtSuffixL, joined NbtSuffixL);
-
-// INTERNAL USE
-struct NbtSuffixF<N>(N);
-impl_cst!(NbtSuffixF, joined NbtSuffixF);
-
-// INTERNAL USE
-struct NbtSuffixD<N>(N);
-impl_cst!(NbtSuffixD, joined NbtSuffixD);
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct NbtNumber<N>(N);
-impl_cst!(NbtNumber, group NbtNumber);
 impl<N: NH> NbtNumber<N> {
     pub fn byte(&self) -> Option<FloatToken<RefNode>> {
         if self
@@ -572,14 +499,7 @@ impl<N: NH> NbtNumber<N> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct NbtString<N>(N);
-impl_cst!(NbtString, group NbtString);
-
-#[derive(Debug, PartialEq, Eq)]
-pub struct NbtBoolean<N>(N);
-impl_cst!(NbtBoolean, group NbtBoolean);
-impl<N: NH> NbtNumber<N> {
+impl<N: NH> NbtBoolean<N> {
     pub fn value(&self) -> Option<bool> {
         let id = self.0.view().first_child()?.string();
         Some(match id {
@@ -590,21 +510,285 @@ impl<N: NH> NbtNumber<N> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct IntRangeArgument<N>(N);
-impl_cst!(IntRangeArgument, group Range);
-impl_cni!(IntRangeArgument IntRange);
+impl<N: NH> IntRangeArgument<N> {
+    /// Gets the lower bound, which is also the upper bound if there is no `..`.
+    pub fn min(&self) -> Option<FloatToken<RefNode<'_>>> {
+        match self.0.view().first_child()?.kind() {
+            SyntaxKind::Token(McTokenKind::DotDot) => None,
+            _ => self.0.as_ref().first_child(),
+        }
+    }
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum McfString<N> {
-    Quoted(N),
-    Unquoted(N),
+    /// Gets the upper bound, which is also the lower bound if there is no `..`.
+    pub fn max(&self) -> Option<FloatToken<RefNode<'_>>> {
+        match self.0.as_ref().first_child::<DotDot<RefNode>>() {
+            Some(dots) => dots.0.next_sibling(),
+            None => self.0.as_ref().first_child(),
+        }
+    }
 }
-impl_cst!(enum McfString,
-    Quoted token QuotedString,
-    Unquoted joined UnquotedString
-);
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct FloatToken<N>(N);
-impl_cst!(FloatToken, joined Float);
+impl<N: NH> FloatToken<N> {
+    pub fn value(&self) -> Option<f64> {
+        self.string().parse().ok()
+    }
+}
+
+impl<N: NH> Integer<N> {
+    pub fn value(&self) -> Option<i32> {
+        self.string().parse().ok()
+    }
+}
+
+impl<N: NH> ResourceLocation<N> {
+    /// Gets the namespace, which is `minecraft` if it is left out.
+    pub fn namespace(&self) -> &str {
+        match self.string().find(':') {
+            Some(ind) => &self.string()[..ind],
+            None => "minecraft",
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self.string().find(':') {
+            Some(ind) => &self.string()[ind + 1..],
+            None => self.string(),
+        }
+    }
+
+    /// Gets the resource location as an identifier, or `None` if its path is empty.
+    pub fn id(&self) -> Option<Identifier> {
+        if self.path().is_empty() {
+            None
+        } else {
+            Some(Identifier::new(self.namespace(), self.path()))
+        }
+    }
+}
+
+impl<N: NH> Uuid<N> {
+    /// Gets the UUID as a number, with the same leniency as the game.
+    ///
+    /// Each of the five parts can be shorter than usual,
+    /// and only its lower bits are kept if it is longer.
+    pub fn value(&self) -> Option<u128> {
+        let mut parts = [0u64; 5];
+        let mut split = self.string().split('-');
+        for part in &mut parts {
+            *part = u64::from_str_radix(split.next()?, 16).ok()?;
+        }
+        if split.next().is_some() {
+            return None;
+        }
+        let most = (parts[0] & 0xffff_ffff) << 32 | (parts[1] & 0xffff) << 16 | parts[2] & 0xffff;
+        let least = (parts[3] & 0xffff) << 48 | parts[4] & 0xffff_ffff_ffff;
+        Some(u128::from(most) << 64 | u128::from(least))
+    }
+}
+
+impl<N: NH> Boolean<N> {
+    pub fn value(&self) -> bool {
+        match self {
+            Boolean::True(_) => true,
+            Boolean::False(_) => false,
+        }
+    }
+}
+
+impl<N: NH> Time<N> {
+    /// Gets the time in ticks, converting from seconds or days.
+    pub fn ticks(&self) -> Option<i64> {
+        let amount: i64 = self.0.view().first_child()?.string().parse().ok()?;
+        let unit = match self.0.view().last_child()?.kind() {
+            SyntaxKind::Joined(McGroupType::TimeS) => 20,
+            SyntaxKind::Joined(McGroupType::TimeD) => 24000,
+            _ => 1,
+        };
+        amount.checked_mul(unit)
+    }
+}
+
+/// The value of a single coordinate, relative to the origin, the executing position,
+/// or the executing rotation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CoordValue {
+    Absolute(f64),
+    Relative(f64),
+    Local(f64),
+}
+
+impl<N: NH> CoordPart<N> {
+    /// Gets the value of the coordinate. An offset that is left out is `0`.
+    pub fn value(&self) -> Option<CoordValue> {
+        let offset = match self.offset() {
+            Some(v) => Some(v.value()?),
+            None => None,
+        };
+        if self.0.as_ref().first_child::<Tilde<RefNode>>().is_some() {
+            Some(CoordValue::Relative(offset.unwrap_or(0.0)))
+        } else if self.0.as_ref().first_child::<Caret<RefNode>>().is_some() {
+            Some(CoordValue::Local(offset.unwrap_or(0.0)))
+        } else {
+            Some(CoordValue::Absolute(offset?))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectorKind {
+    /// `@p`
+    NearestPlayer,
+    /// `@a`
+    AllPlayers,
+    /// `@r`
+    RandomPlayer,
+    /// `@s`
+    ExecutingEntity,
+    /// `@e`
+    AllEntities,
+}
+
+impl<N: NH> Selector<N> {
+    /// Gets the kind of the selector, which is `None` for player names and UUIDs.
+    pub fn kind(&self) -> Option<SelectorKind> {
+        self.0.view().children().find_map(|c| match c.kind() {
+            SyntaxKind::Joined(McGroupType::SelectorModP) => Some(SelectorKind::NearestPlayer),
+            SyntaxKind::Joined(McGroupType::SelectorModA) => Some(SelectorKind::AllPlayers),
+            SyntaxKind::Joined(McGroupType::SelectorModR) => Some(SelectorKind::RandomPlayer),
+            SyntaxKind::Joined(McGroupType::SelectorModS) => Some(SelectorKind::ExecutingEntity),
+            SyntaxKind::Joined(McGroupType::SelectorModE) => Some(SelectorKind::AllEntities),
+            _ => None,
+        })
+    }
+
+    pub fn arguments(&self) -> impl Iterator<Item = SelectorArgumentEntry<RefNode<'_>>> + '_ {
+        self.0
+            .as_ref()
+            .first_child::<SelectorArgument<RefNode>>()
+            .into_iter()
+            .flat_map(|args| args.0.children())
+    }
+}
+
+impl<N: NH> SelectorArgumentEntry<N> {
+    /// Checks whether the value is inverted with `!`.
+    pub fn inverted(&self) -> bool {
+        self.0.as_ref().first_child::<Excl<RefNode>>().is_some()
+    }
+
+    /// Checks whether the value is a tag, such as `#minecraft:skeletons`.
+    pub fn is_tag(&self) -> bool {
+        self.0.as_ref().first_child::<Hash<RefNode>>().is_some()
+    }
+
+    pub fn value(&self) -> Option<SelectorValue<RefNode<'_>>> {
+        self.0
+            .as_ref()
+            .first_child::<Eq<RefNode>>()?
+            .0
+            .next_sibling()
+    }
+}
+
+impl<N: NH> SelectorArgumentMapEntry<N> {
+    /// Gets the key, which is an objective, an advancement or a criterion.
+    pub fn key(&self) -> Option<&str> {
+        let key = self.0.view().first_child()?;
+        match key.kind() {
+            SyntaxKind::Joined(McGroupType::ResourceLocation)
+            | SyntaxKind::Joined(McGroupType::UnquotedString) => Some(key.string()),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the value is inverted with `!`.
+    pub fn inverted(&self) -> bool {
+        self.0.as_ref().first_child::<Excl<RefNode>>().is_some()
+    }
+
+    pub fn value(&self) -> Option<SelectorValue<RefNode<'_>>> {
+        self.0
+            .as_ref()
+            .first_child::<Eq<RefNode>>()?
+            .0
+            .next_sibling()
+    }
+}
+
+impl<N: NH> BlockState<N> {
+    /// Checks whether the block is a tag, such as `#minecraft:logs`.
+    pub fn is_tag(&self) -> bool {
+        self.0.as_ref().first_child::<Hash<RefNode>>().is_some()
+    }
+
+    pub fn properties(&self) -> impl Iterator<Item = BlockStateProperty<RefNode<'_>>> + '_ {
+        self.0
+            .as_ref()
+            .first_child::<BlockStateArguments<RefNode>>()
+            .into_iter()
+            .flat_map(|args| args.0.children())
+    }
+}
+
+impl<N: NH> BlockStateProperty<N> {
+    /// Gets the value of the property, which is `None` if it was left out.
+    pub fn value(&self) -> Option<UnquotedString<RefNode<'_>>> {
+        let value: UnquotedString<RefNode> = self
+            .0
+            .as_ref()
+            .first_child::<Eq<RefNode>>()?
+            .0
+            .next_sibling()?;
+        if value.string().is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+}
+
+impl<N: NH> ItemPredicate<N> {
+    /// Checks whether the item is a tag, such as `#minecraft:logs`.
+    pub fn is_tag(&self) -> bool {
+        self.0.as_ref().first_child::<Hash<RefNode>>().is_some()
+    }
+}
+
+impl<N: NH> Function<N> {
+    /// Checks whether the function is a tag, such as `#minecraft:tick`.
+    pub fn is_tag(&self) -> bool {
+        self.0.as_ref().first_child::<Hash<RefNode>>().is_some()
+    }
+}
+
+impl<N: NH> NbtPathSegment<N> {
+    /// Gets the name of the key, or `None` if the segment is an index.
+    /// Quoted names keep their quotes.
+    pub fn name(&self) -> Option<&str> {
+        let name = self
+            .0
+            .view()
+            .children()
+            .find(|c| NbtPathName::<RefNode>::can_cast(*c))?;
+        Some(name.string())
+    }
+}
+
+impl<N: NH> JsonObjectEntry<N> {
+    /// Gets the key, including its quotes.
+    pub fn key(&self) -> Option<&str> {
+        let key = self.0.view().first_child()?;
+        match key.kind() {
+            SyntaxKind::Token(McTokenKind::QuotedString) => Some(key.string()),
+            _ => None,
+        }
+    }
+
+    pub fn value(&self) -> Option<JsonValue<RefNode<'_>>> {
+        self.0
+            .as_ref()
+            .first_child::<Colon<RefNode>>()?
+            .0
+            .next_sibling()
+    }
+}
@@ -1,25 +1,18 @@
 use super::*;
 use crate::syntax::{group::McGroupType::*, McParser, McTokenKind::*};
-use mcfunction_parse::parser::StartInfo::{self, Skip};
+use mcfunction_parse::{
+    parser::{
+        Progress,
+        StartInfo::{self, Skip},
+    },
+    tokenset,
+};
 
 pub fn state(p: &mut McParser) {
     let mk = p.start(BlockState, StartInfo::None);
     resource_location(p);
     if p.at(LBracket) {
-        let argmk = p.start(BlockStateArguments, Skip);
-        if !p.at(RBracket) {
-            loop {
-                uq_string(p);
-                p.expect(Eq);
-                uq_string(p);
-                if p.at(RBracket) {
-                    break;
-                }
-                p.expect(Comma);
-            }
-        }
-        p.expect(RBracket);
-        p.finish(argmk);
+        arguments(p);
     }
     if p.at(LCurly) {
         nbt::compound(p);
@@ -32,23 +25,57 @@ pub fn predicate(p: &mut McParser) {
     p.eat(Hash);
     resource_location(p);
     if p.at(LBracket) {
-        let argmk = p.start(BlockStateArguments, Skip);
-        if !p.at(RBracket) {
-            loop {
-                uq_string(p);
-                p.expect(Eq);
-                uq_string(p);
-                if p.at(RBracket) {
-                    break;
-                }
-                p.expect(Comma);
-            }
-        }
-        p.expect(RBracket);
-        p.finish(argmk);
+        arguments(p);
     }
     if p.at(LCurly) {
         nbt::compound(p);
     }
     p.finish(mk);
 }
+
+fn arguments(p: &mut McParser) {
+    let argmk = p.start(BlockStateArguments, Skip);
+    p.expect(LBracket);
+    if !p.at(RBracket) {
+        p.push_recovery(tokenset![Comma, RBracket]);
+        let mut progress = Progress::new();
+        while p.progressed(&mut progress) {
+            let propmk = p.start(BlockStateProperty, Skip);
+            uq_string(p);
+            p.expect(Eq);
+            uq_string(p);
+            p.finish(propmk);
+            if !list_comma(p, RBracket) {
+                break;
+            }
+        }
+        p.pop_recovery();
+    }
+    p.expect(RBracket);
+    p.finish(argmk);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::testing::{format_astnode, parse};
+
+    use insta::assert_snapshot;
+
+    macro_rules! block_test {
+        ($name:ident, $e:expr) => {
+            #[test]
+            fn $name() {
+                assert_snapshot!(parse_block($e));
+            }
+        };
+    }
+
+    fn parse_block(i: &str) -> String {
+        format_astnode(parse(i, super::predicate).root(), 0)
+    }
+
+    block_test!(id_only, "minecraft:stone");
+    block_test!(properties, "oak_stairs[facing=east, half=top]");
+    block_test!(tag_nbt, "#minecraft:beds[part=head]{Color:1b}");
+    block_test!(property_novalue, "oak_stairs[facing=,half=top]");
+}
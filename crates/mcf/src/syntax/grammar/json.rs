@@ -4,7 +4,10 @@ use crate::syntax::{
     McParser,
     McTokenKind::*,
 };
-use mcfunction_parse::{parser::StartInfo::Skip, TokenSet};
+use mcfunction_parse::{
+    parser::{Progress, StartInfo::Skip},
+    tokenset,
+};
 
 const JSON_NULL: &[(&str, McGroupType)] = &[("null", JsonNull)];
 
@@ -14,31 +17,19 @@ pub fn object(p: &mut McParser) {
     if p.eat(RCurly) {
         p.finish(objmk);
     } else {
-        loop {
+        p.push_recovery(tokenset![Comma, RCurly]);
+        let mut progress = Progress::new();
+        while p.progressed(&mut progress) {
             let entmk = p.start(JsonObjectEntry, Skip);
             if p.expect(QuotedString) && p.expect(Colon) {
                 value(p);
             }
             p.finish(entmk);
-            if p.at(RCurly) {
+            if !list_comma(p, RCurly) {
                 break;
             }
-            match (p.expect(Comma), p.at(Eof)) {
-                (true, true) => {
-                    p.expect(QuotedString);
-                    break;
-                }
-                (false, false) => {
-                    if p.bump_recover(TokenSet::empty()) {
-                        break;
-                    }
-                }
-                (true, false) => (),
-                (false, true) => {
-                    break;
-                }
-            }
         }
+        p.pop_recovery();
         p.expect(RCurly);
         p.finish(objmk);
     }
@@ -50,27 +41,15 @@ pub fn array(p: &mut McParser) {
     if p.eat(RBracket) {
         p.finish(arrmk);
     } else {
-        loop {
+        p.push_recovery(tokenset![Comma, RBracket]);
+        let mut progress = Progress::new();
+        while p.progressed(&mut progress) {
             value(p);
-            if p.at(RBracket) {
+            if !list_comma(p, RBracket) {
                 break;
             }
-            match (p.expect(Comma), p.at(Eof)) {
-                (true, true) => {
-                    value(p);
-                    break;
-                }
-                (false, false) => {
-                    if p.bump_recover(TokenSet::empty()) {
-                        break;
-                    }
-                }
-                (true, false) => (),
-                (false, true) => {
-                    break;
-                }
-            }
         }
+        p.pop_recovery();
         p.expect(RBracket);
         p.finish(arrmk);
     }
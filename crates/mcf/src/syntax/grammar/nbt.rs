@@ -5,7 +5,10 @@ use crate::syntax::{
     McTokenKind::*,
 };
 use mcfunction_parse::{
-    parser::StartInfo::{self, Join, Skip},
+    parser::{
+        Progress,
+        StartInfo::{self, Join, Skip},
+    },
     tokenset,
 };
 
@@ -42,12 +45,15 @@ pub fn value(p: &mut McParser) {
             }
             p.bump();
         }
-        while !p.at(RBracket) {
+        p.push_recovery(tokenset![Comma, RBracket]);
+        let mut progress = Progress::new();
+        while !p.at(RBracket) && p.progressed(&mut progress) {
             value(p);
-            if !p.eat(Comma) {
+            if !list_comma(p, RBracket) {
                 break;
             }
         }
+        p.pop_recovery();
         p.expect(RBracket);
         p.finish(mk);
     } else if p.at_keyword(BOOLEAN) {
@@ -89,16 +95,19 @@ pub fn compound(p: &mut McParser) {
     let cpdmk = p.start(NbtCompound, Skip);
 
     p.bump();
-    while !p.at(RCurly) {
+    p.push_recovery(tokenset![Comma, RCurly]);
+    let mut progress = Progress::new();
+    while !p.at(RCurly) && p.progressed(&mut progress) {
         let enmk = p.start(NbtCompoundEntry, Skip);
         string(p);
         p.expect(Colon);
         value(p);
         p.finish(enmk);
-        if !p.eat(Comma) {
+        if !list_comma(p, RCurly) {
             break;
         }
     }
+    p.pop_recovery();
     p.expect(RCurly);
     p.finish(cpdmk);
 }
@@ -106,7 +115,8 @@ pub fn compound(p: &mut McParser) {
 pub fn path(p: &mut McParser) {
     let mk = p.start(NbtPath, StartInfo::None);
     let mut start = true;
-    loop {
+    let mut progress = Progress::new();
+    while p.progressed(&mut progress) {
         let vmk = p.start(NbtPathSegment, StartInfo::None);
         if p.at(LBracket) {
             let indmk = p.start(NbtPathIndex, StartInfo::Skip);
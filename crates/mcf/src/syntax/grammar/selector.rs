@@ -5,8 +5,11 @@ use crate::syntax::{
     McTokenKind::*,
 };
 use mcfunction_parse::{
-    parser::StartInfo::{self, Skip},
-    TokenSet,
+    parser::{
+        Progress,
+        StartInfo::{self, Skip},
+    },
+    tokenset, TokenSet,
 };
 const SELECTOR_TYPE: &[(&str, McGroupType)] = &[
     ("p", SelectorModP),
@@ -16,12 +19,110 @@ const SELECTOR_TYPE: &[(&str, McGroupType)] = &[
     ("e", SelectorModE),
 ];
 
+const SORT: &[(&str, McGroupType)] = &[
+    ("nearest", SelectorSort),
+    ("furthest", SelectorSort),
+    ("random", SelectorSort),
+    ("arbitrary", SelectorSort),
+];
+
+const GAMEMODE: &[(&str, McGroupType)] = &[
+    ("survival", SelectorGamemode),
+    ("creative", SelectorGamemode),
+    ("adventure", SelectorGamemode),
+    ("spectator", SelectorGamemode),
+];
+
+/// The grammar of the value of a selector argument.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectorValueType {
+    Float,
+    Integer,
+    Range,
+    /// An unquoted string, which can be empty
+    Word,
+    /// A quoted or unquoted string
+    String,
+    /// A resource location, which can be a tag if `tag` is set
+    ResourceLocation {
+        tag: bool,
+    },
+    Keyword(&'static [(&'static str, McGroupType)]),
+    NbtCompound,
+    /// A map of objectives to ranges
+    Scores,
+    /// A map of advancements to booleans, or to maps of criteria to booleans
+    Advancements,
+}
+
+/// How many times a selector argument can be given.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectorRepeat {
+    Once,
+    Many,
+    /// Any number of inverted values, or a single value that isn't inverted as the last one
+    InvertedOnly,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SelectorArgumentInfo {
+    pub name: &'static str,
+    pub value: SelectorValueType,
+    pub invertible: bool,
+    pub repeat: SelectorRepeat,
+    /// Whether the argument can be used with `@s`
+    pub allow_self: bool,
+}
+
+macro_rules! selector_args {
+    ($($name:literal: $value:expr, $invertible:literal, $repeat:ident $(, $no_self:ident)?;)*) => {
+        /// The selector arguments of the game.
+        pub const SELECTOR_ARGUMENTS: &[SelectorArgumentInfo] = &[
+            $(SelectorArgumentInfo {
+                name: $name,
+                value: $value,
+                invertible: $invertible,
+                repeat: SelectorRepeat::$repeat,
+                allow_self: selector_args!(@self $($no_self)?),
+            },)*
+        ];
+    };
+    (@self) => { true };
+    (@self no_self) => { false };
+}
+
+selector_args! {
+    "x": SelectorValueType::Float, false, Once;
+    "y": SelectorValueType::Float, false, Once;
+    "z": SelectorValueType::Float, false, Once;
+    "dx": SelectorValueType::Float, false, Once;
+    "dy": SelectorValueType::Float, false, Once;
+    "dz": SelectorValueType::Float, false, Once;
+    "distance": SelectorValueType::Range, false, Once;
+    "level": SelectorValueType::Range, false, Once;
+    "x_rotation": SelectorValueType::Range, false, Once;
+    "y_rotation": SelectorValueType::Range, false, Once;
+    "limit": SelectorValueType::Integer, false, Once, no_self;
+    "sort": SelectorValueType::Keyword(SORT), false, Once, no_self;
+    "gamemode": SelectorValueType::Keyword(GAMEMODE), true, InvertedOnly;
+    "name": SelectorValueType::String, true, InvertedOnly;
+    "team": SelectorValueType::Word, true, InvertedOnly;
+    "type": SelectorValueType::ResourceLocation { tag: true }, true, InvertedOnly;
+    "tag": SelectorValueType::Word, true, Many;
+    "nbt": SelectorValueType::NbtCompound, true, Many;
+    "predicate": SelectorValueType::ResourceLocation { tag: false }, true, Many;
+    "scores": SelectorValueType::Scores, false, Once;
+    "advancements": SelectorValueType::Advancements, false, Once;
+}
+
+/// Finds a selector argument by its name.
+pub fn selector_argument(name: &str) -> Option<&'static SelectorArgumentInfo> {
+    SELECTOR_ARGUMENTS.iter().find(|a| a.name == name)
+}
+
 pub fn game_profile(p: &mut McParser) {
-    let mk = p.start(Selector, StartInfo::None);
-    if !p.try_token(uuid_tk, Uuid) {
-        uq_string(p);
-    }
-    p.finish(mk);
+    // Game profiles can be selected the same ways as entities, the game only checks that they are players
+    entity(p);
 }
 
 pub fn entity(p: &mut McParser) {
@@ -40,7 +141,7 @@ pub fn score_holder(p: &mut McParser) {
         selector(p);
     } else if !p.try_token(uuid_tk, Uuid) {
         let nmp = p.start(UnquotedString, StartInfo::Join);
-        while !p.at(Whitespace) {
+        while p.not_at(Whitespace) {
             p.bump();
         }
         p.finish(nmp);
@@ -57,20 +158,27 @@ pub fn selector(p: &mut McParser) {
         let argsmk = p.start(SelectorArgument, Skip);
         p.bump();
         if !p.at(RBracket) {
-            loop {
+            p.push_recovery(tokenset![Comma, RBracket]);
+            let mut progress = Progress::new();
+            while p.progressed(&mut progress) {
                 let argmk = p.start(SelectorArgumentEntry, Skip);
+                let arg = SELECTOR_ARGUMENTS.iter().find(|a| {
+                    p.at_keyword(&[(a.name, UnquotedString)])
+                        && !tokenset!(p.nth_no_skip(1) => ALLOWED_UQ_STRING)
+                });
                 uq_string(p);
                 p.expect(Eq);
-                seletor_arg_value(p);
-                p.finish(argmk);
-                if p.at(RBracket) {
-                    break;
+                match arg {
+                    Some(arg) => selector_arg_typed(p, arg.value),
+                    // Unknown arguments are reported when validating
+                    None => seletor_arg_value(p),
                 }
-                p.expect(Comma);
-                if p.at(Eof) {
+                p.finish(argmk);
+                if !list_comma(p, RBracket) {
                     break;
                 }
             }
+            p.pop_recovery();
         }
         p.expect(RBracket);
         p.finish(argsmk);
@@ -85,20 +193,19 @@ pub fn seletor_arg_value(p: &mut McParser) {
         let mapmk = p.start(SelectorArgumentMap, Skip);
         p.bump();
         if !p.at(RCurly) {
-            loop {
+            p.push_recovery(tokenset![Comma, RCurly]);
+            let mut progress = Progress::new();
+            while p.progressed(&mut progress) {
                 let argmk = p.start(SelectorArgumentMapEntry, Skip);
                 resource_location(p);
                 p.expect(Eq);
                 seletor_arg_value(p);
                 p.finish(argmk);
-                if p.at(RCurly) {
-                    break;
-                }
-                p.expect(Comma);
-                if p.at(Eof) {
+                if !list_comma(p, RCurly) {
                     break;
                 }
             }
+            p.pop_recovery();
         }
         p.expect(RCurly);
         p.finish(mapmk);
@@ -107,6 +214,83 @@ pub fn seletor_arg_value(p: &mut McParser) {
     }
 }
 
+/// Parses the value of a known selector argument with its grammar.
+pub fn selector_arg_typed(p: &mut McParser, value: SelectorValueType) {
+    p.eat(Excl);
+    match value {
+        SelectorValueType::Float => float(p),
+        SelectorValueType::Integer => integer(p),
+        SelectorValueType::Range => range(p),
+        SelectorValueType::Word => uq_string(p),
+        SelectorValueType::String => string(p),
+        SelectorValueType::ResourceLocation { tag } => {
+            if tag {
+                p.eat(Hash);
+            }
+            resource_location(p);
+        }
+        SelectorValueType::Keyword(kws) => {
+            if !p.expect_keyword(kws) {
+                p.bump_recover(tokenset![Comma, RBracket]);
+            }
+        }
+        SelectorValueType::NbtCompound => {
+            if p.at(LCurly) {
+                super::nbt::compound(p);
+            } else {
+                p.error(NbtCompound);
+            }
+        }
+        SelectorValueType::Scores => selector_map(p, |p| {
+            uq_string(p);
+            p.expect(Eq);
+            range(p);
+        }),
+        SelectorValueType::Advancements => selector_map(p, |p| {
+            resource_location(p);
+            p.expect(Eq);
+            if p.at(LCurly) {
+                selector_map(p, |p| {
+                    uq_string(p);
+                    p.expect(Eq);
+                    boolean(p);
+                });
+            } else {
+                boolean(p);
+            }
+        }),
+    }
+}
+
+fn selector_map(p: &mut McParser, mut entry: impl FnMut(&mut McParser)) {
+    let mapmk = p.start(SelectorArgumentMap, Skip);
+    if !p.expect(LCurly) {
+        p.cancel(mapmk);
+        return;
+    }
+    if !p.at(RCurly) {
+        p.push_recovery(tokenset![Comma, RCurly]);
+        let mut progress = Progress::new();
+        while p.progressed(&mut progress) {
+            let argmk = p.start(SelectorArgumentMapEntry, Skip);
+            entry(p);
+            p.finish(argmk);
+            if !list_comma(p, RCurly) {
+                break;
+            }
+        }
+        p.pop_recovery();
+    }
+    p.expect(RCurly);
+    p.finish(mapmk);
+}
+
+fn boolean(p: &mut McParser) {
+    if !p.expect_keyword(BOOLEAN) {
+        p.bump_recover(tokenset![Comma, RCurly]);
+    }
+}
+
 pub fn try_range_suffix(p: &mut McParser) -> bool {
     let mk = p.start(Range, StartInfo::None);
     if p.eat(DotDot) {
@@ -168,4 +352,16 @@ mod tests {
     selector_test!(unclosed_arg_key, "@p[type");
     selector_test!(arg_unclosed_map_nokey, "@s[score={");
     selector_test!(arg_unclosed_map_noeq, "@p[advancements={hello");
+
+    selector_test!(
+        typed_values,
+        "@e[x=1.5,limit=2,sort=nearest,name=\"a b\",team=,type=#minecraft:skeletons]"
+    );
+    selector_test!(typed_nbt, "@e[nbt=!{OnGround:1b},gamemode=!creative]");
+    selector_test!(typed_invalid, "@e[limit=a,sort=closest,nbt=1]");
+    selector_test!(
+        typed_advancements,
+        "@a[advancements={story/root=true,a:b={c=false}}]"
+    );
+    selector_test!(unknown_key, "@e[foo=bar,tagged=a]");
 }
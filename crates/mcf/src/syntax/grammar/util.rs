@@ -45,6 +45,33 @@ pub fn message(p: &mut McParser) {
     p.finish(mk);
 }
 
+/// Expects the comma after an entry of a list that is closed by `close`.
+///
+/// Returns whether another entry follows. At the EOF, the missing `close`
+/// is left to be reported by the list itself. Without a comma, the parser reports it
+/// unless the entry has reported an error already, and recovers
+/// by skipping to the next comma or `close`, unless an enclosing rule can
+/// continue from a token before that. The list should add both of those tokens
+/// with [`push_recovery`] while it parses its entries.
+///
+/// [`push_recovery`]: ../../../mcfunction_parse/parser/struct.Parser.html#method.push_recovery
+pub fn list_comma(p: &mut McParser, close: McTokenKind) -> bool {
+    if p.at(close) || p.at(Eof) {
+        return false;
+    }
+    if !p.eat(Comma) {
+        // A broken entry has already reported an error for this token
+        if !p.errored() {
+            p.expect(Comma);
+        }
+        p.recover(tokenset![Comma, close]);
+        if !p.eat(Comma) {
+            return false;
+        }
+    }
+    !p.at(Eof)
+}
+
 pub fn resource_location(p: &mut McParser) {
     if !p.try_token(resource_location_tk, ResourceLocation) {
         p.error(ResourceLocation);
@@ -101,7 +128,61 @@ pub const ALLOWED_UQ_STRING: TokenSet<McTokenKind> =
     tokenset![Digits, Word, Dash, Plus, Dot, DotDot];
 
 pub const OPERATION: TokenSet<McTokenKind> =
-    tokenset![AddAssign, SubAssign, MulAssign, DivAssign, ModAssign, Lte, Gte, Swap];
+    tokenset![Eq, AddAssign, SubAssign, MulAssign, DivAssign, ModAssign, Swap, Lt, Gt];
+
+pub const COLOR: &[(&str, McGroupType)] = &[
+    ("black", Color),
+    ("dark_blue", Color),
+    ("dark_green", Color),
+    ("dark_aqua", Color),
+    ("dark_red", Color),
+    ("dark_purple", Color),
+    ("gold", Color),
+    ("gray", Color),
+    ("dark_gray", Color),
+    ("blue", Color),
+    ("green", Color),
+    ("aqua", Color),
+    ("red", Color),
+    ("light_purple", Color),
+    ("yellow", Color),
+    ("white", Color),
+    ("reset", Color),
+];
+
+// Every color except `reset`, which is last
+const TEAM_COLOR: &[(&str, McGroupType)] = COLOR.split_at(COLOR.len() - 1).0;
+
+// Each axis can only be used once, in any order
+pub const SWIZZLE: &[(&str, McGroupType)] = &[
+    ("x", Swizzle),
+    ("y", Swizzle),
+    ("z", Swizzle),
+    ("xy", Swizzle),
+    ("xz", Swizzle),
+    ("yx", Swizzle),
+    ("yz", Swizzle),
+    ("zx", Swizzle),
+    ("zy", Swizzle),
+    ("xyz", Swizzle),
+    ("xzy", Swizzle),
+    ("yxz", Swizzle),
+    ("yzx", Swizzle),
+    ("zxy", Swizzle),
+    ("zyx", Swizzle),
+];
+
+pub const ENTITY_ANCHOR: &[(&str, McGroupType)] = &[("eyes", EntityAnchor), ("feet", EntityAnchor)];
+
+const SCOREBOARD_SLOT: &[(&str, McGroupType)] = &[
+    ("list", ScoreboardSlot),
+    ("sidebar", ScoreboardSlot),
+    ("belowName", ScoreboardSlot),
+];
+
+const SIDEBAR: &[(&str, McGroupType)] = &[("sidebar", ScoreboardSlot)];
+
+const TEAM: &[(&str, McGroupType)] = &[("team", ScoreboardSlot)];
 
 pub const BOOLEAN: &[(&str, McGroupType)] = &[("true", BooleanTrue), ("false", BooleanFalse)];
 
@@ -133,6 +214,32 @@ pub fn uq_string_ne_tk(p: &mut McTokenParser) -> Option<()> {
     Some(())
 }
 
+pub fn scoreboard_slot(p: &mut McParser) {
+    if !p.try_token(scoreboard_slot_tk, ScoreboardSlot) {
+        p.error(ScoreboardSlot);
+    }
+}
+
+pub fn scoreboard_slot_tk(p: &mut McTokenParser) -> Option<()> {
+    // The sidebar can also be shown only to the members of teams with a color
+    if p.expect_kw(SIDEBAR).is_some() {
+        if p.eat(Dot) {
+            p.expect_kw(TEAM)?;
+            p.expect(Dot)?;
+            p.expect_kw(TEAM_COLOR)?;
+        }
+        Some(())
+    } else {
+        p.expect_kw(SCOREBOARD_SLOT)
+    }
+}
+
+pub fn operation(p: &mut McParser) {
+    if !p.try_token(|p| p.expect_tokens(OPERATION), Operation) {
+        p.error(Operation);
+    }
+}
+
 pub fn time(p: &mut McParser) {
     let mk = p.start(Time, StartInfo::None);
     p.expect(Digits);
@@ -141,36 +248,18 @@ pub fn time(p: &mut McParser) {
 }
 
 pub fn uuid_tk(p: &mut McTokenParser) -> Option<()> {
-    let mut empty = true;
-    while p.eat(Digits) || p.eat_kw(HEX_CHAR) {
-        empty = false
-    }
-    if empty {
-        return None;
-    }
-    p.expect(Dash)?;
-    let mut empty = true;
-    while p.eat(Digits) || p.eat_kw(HEX_CHAR) {
-        empty = false
-    }
-    if empty {
-        return None;
-    }
-    p.expect(Dash)?;
-    let mut empty = true;
-    while p.eat(Digits) || p.eat_kw(HEX_CHAR) {
-        empty = false
-    }
-    if empty {
-        return None;
-    }
-    p.expect(Dash)?;
-    let mut empty = true;
-    while p.eat(Digits) || p.eat_kw(HEX_CHAR) {
-        empty = false
-    }
-    if empty {
-        return None;
+    // A UUID has five hexadecimal parts separated by dashes
+    for part in 0..5 {
+        if part != 0 {
+            p.expect(Dash)?;
+        }
+        let mut empty = true;
+        while p.eat(Digits) || p.eat_kw(HEX_CHAR) {
+            empty = false
+        }
+        if empty {
+            return None;
+        }
     }
     Some(())
 }
@@ -245,6 +334,15 @@ mod tests {
     util_test!(uq_string_mixed, "123qvr-wvg35.+", uq_string);
     util_test!(uq_string_leftover, "hello_word; rest of input", uq_string);
 
+    util_test!(slot_list, "list", scoreboard_slot);
+    util_test!(slot_team, "sidebar.team.dark_red", scoreboard_slot);
+    util_test!(slot_team_reset, "sidebar.team.reset", scoreboard_slot);
+    util_test!(slot_unknown, "belowname", scoreboard_slot);
+
+    util_test!(operation_swap, "><", operation);
+    util_test!(operation_min, "<", operation);
+    util_test!(operation_lte, "<=", operation);
+
     util_test!(unsigned_int, "642345", integer);
     util_test!(signed_int, "-23445", integer);
     util_test!(int_only_dash, "-", integer);
@@ -1,3 +1,4 @@
+use mcfunction_parse::GroupType;
 use util::commands::Index;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -39,10 +40,13 @@ pub enum McGroupType {
     SelectorModR,
     SelectorModS,
     SelectorModE,
+    SelectorSort,
+    SelectorGamemode,
 
     // Block states
     BlockState,
     BlockStateArguments,
+    BlockStateProperty,
 
     ItemStack,
     ItemPredicate,
@@ -68,6 +72,13 @@ pub enum McGroupType {
     TimeT,
     TimeD,
 
+    // Keyword arguments
+    Color,
+    Swizzle,
+    EntityAnchor,
+    ScoreboardSlot,
+    Operation,
+
     Coord,
     CoordPart,
 
@@ -81,3 +92,140 @@ pub enum McGroupType {
     // Special error type that signifies an error
     Error,
 }
+
+impl GroupType for McGroupType {
+    fn description(self) -> &'static str {
+        use McGroupType::*;
+
+        match self {
+            File => "a function file",
+            CommandNode(_) => "a command argument",
+            Command => "a command",
+            NbtCompound => "an NBT compound",
+            NbtSequence => "an NBT list or array",
+            NbtNumber => "an NBT number",
+            NbtString => "an NBT string",
+            NbtBoolean => "an NBT boolean",
+            NbtSuffixB => "the byte suffix `b`",
+            NbtSuffixS => "the short suffix `s`",
+            NbtSuffixL => "the long suffix `l`",
+            NbtSuffixF => "the float suffix `f`",
+            NbtSuffixD => "the double suffix `d`",
+            NbtPrefixB => "the byte array prefix `B;`",
+            NbtPrefixI => "the int array prefix `I;`",
+            NbtPrefixL => "the long array prefix `L;`",
+            NbtCompoundEntry => "an NBT compound entry",
+            NbtPath => "an NBT path",
+            NbtPathSegment => "an NBT path segment",
+            NbtPathIndex => "an NBT path index",
+            Selector => "an entity selector",
+            SelectorArgument => "selector arguments",
+            SelectorArgumentEntry => "a selector argument",
+            SelectorArgumentMap => "a selector argument map",
+            SelectorArgumentMapEntry => "a selector argument map entry",
+            SelectorModP => "the nearest player selector `@p`",
+            SelectorModA => "the all players selector `@a`",
+            SelectorModR => "the random player selector `@r`",
+            SelectorModS => "the executing entity selector `@s`",
+            SelectorModE => "the all entities selector `@e`",
+            SelectorSort => "a sort order",
+            SelectorGamemode => "a game mode",
+            BlockState => "a block state",
+            BlockStateArguments => "block state properties",
+            BlockStateProperty => "a block state property",
+            ItemStack => "an item stack",
+            ItemPredicate => "an item predicate",
+            Comment => "a comment",
+            Function => "a function name",
+            JsonObject => "a JSON object",
+            JsonObjectEntry => "a JSON object entry",
+            JsonList => "a JSON array",
+            JsonNull => "JSON null",
+            Integer => "an integer",
+            Float => "a number",
+            UnquotedString => "an unquoted string",
+            ResourceLocation => "a resource location",
+            Range => "a range",
+            Uuid => "a UUID",
+            Time => "a time",
+            TimeS => "the seconds suffix `s`",
+            TimeT => "the ticks suffix `t`",
+            TimeD => "the days suffix `d`",
+            Color => "a color",
+            Swizzle => "a set of axes",
+            EntityAnchor => "an entity anchor",
+            ScoreboardSlot => "a scoreboard display slot",
+            Operation => "a scoreboard operation",
+            Coord => "coordinates",
+            CoordPart => "a coordinate",
+            BooleanTrue => "`true`",
+            BooleanFalse => "`false`",
+            FloatSciExpUpper => "the exponent `E`",
+            FloatSciExpLower => "the exponent `e`",
+            Error => "valid syntax",
+        }
+    }
+
+    fn example(self) -> Option<&'static str> {
+        use McGroupType::*;
+
+        Some(match self {
+            File | CommandNode(_) | Error => return None,
+            // Keywords already name their text in the description
+            NbtSuffixB | NbtSuffixS | NbtSuffixL | NbtSuffixF | NbtSuffixD | NbtPrefixB
+            | NbtPrefixI | NbtPrefixL | SelectorModP | SelectorModA | SelectorModR
+            | SelectorModS | SelectorModE | TimeS | TimeT | TimeD | BooleanTrue | BooleanFalse
+            | FloatSciExpUpper | FloatSciExpLower => return None,
+            Command => "say hello",
+            NbtCompound => "{CustomName:\"a\",Count:1b}",
+            NbtSequence => "[1, 2, 3]",
+            NbtNumber => "1.5f",
+            NbtString => "\"hello\"",
+            NbtBoolean => "true",
+            NbtCompoundEntry => "Count:1b",
+            NbtPath => "Inventory[0].id",
+            NbtPathSegment => "Inventory",
+            NbtPathIndex => "[0]",
+            Selector => "@e[type=pig]",
+            SelectorArgument => "[type=pig,limit=1]",
+            SelectorArgumentEntry => "type=pig",
+            SelectorArgumentMap => "{foo=true}",
+            SelectorArgumentMapEntry => "foo=true",
+            SelectorSort => "nearest",
+            SelectorGamemode => "survival",
+            BlockState => "minecraft:oak_stairs[facing=east]",
+            BlockStateArguments => "[facing=east]",
+            BlockStateProperty => "facing=east",
+            ItemStack => "minecraft:diamond_sword{Damage:1}",
+            ItemPredicate => "#minecraft:logs",
+            Comment => "# comment",
+            Function => "foo:bar",
+            JsonObject => "{\"text\":\"hello\"}",
+            JsonObjectEntry => "\"text\":\"hello\"",
+            JsonList => "[\"a\", \"b\"]",
+            JsonNull => "null",
+            Integer => "42",
+            Float => "1.5",
+            UnquotedString => "foo",
+            ResourceLocation => "minecraft:stone",
+            Range => "1..5",
+            Uuid => "dd12be42-52a9-4a91-a8a1-11c01849e498",
+            Time => "5s",
+            Color => "red",
+            Swizzle => "xz",
+            EntityAnchor => "eyes",
+            ScoreboardSlot => "sidebar.team.red",
+            Operation => "+=",
+            Coord => "~ ~1 ~",
+            CoordPart => "~1",
+        })
+    }
+
+    fn name(self) -> String {
+        match self {
+            // The index depends on the order that the commands were loaded in
+            McGroupType::CommandNode(_) => "CommandNode".to_owned(),
+            _ => format!("{:?}", self),
+        }
+    }
+}
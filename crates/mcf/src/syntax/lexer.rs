@@ -192,8 +192,9 @@ pub fn tokenize_str(src: &str) -> Vec<Vec<Token<McTokenKind>>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::syntax::testing::format_sk_list;
+    use crate::syntax::{testing::format_sk_list, McTokenKind};
     use insta::assert_snapshot;
+    use mcfunction_parse::{LineCol, LineIndex};
 
     macro_rules! lex_test {
         ($name:ident, $e:expr) => {
@@ -234,4 +235,55 @@ mod tests {
     lex_test!(unclosed_string_newline, "\"hello world\ngoodbye\"");
 
     lex_test!(single_cr_linebreak, "testing\rbad newline");
+
+    macro_rules! line_index_test {
+        ($name:ident, $e:expr, $utf16_ends:expr) => {
+            #[test]
+            fn $name() {
+                line_index($e, &$utf16_ends);
+            }
+        };
+    }
+
+    // Checks that the index agrees with the spans and offsets of every token,
+    // and that `utf16_ends` are the UTF-16 ends of the quoted strings
+    fn line_index(src: &str, utf16_ends: &[LineCol]) {
+        let index = LineIndex::new(src);
+        let mut ends = vec![];
+        for tk in super::tokenize_str(src).into_iter().flatten() {
+            let span = tk.span();
+            assert_eq!(index.line_col(tk.start()), Some(span.start()));
+            assert_eq!(index.offset(span.start()), Some(tk.start()));
+            for pos in &[span.start(), span.end()] {
+                let utf16 = index.to_utf16(*pos).unwrap();
+                assert_eq!(index.from_utf16(utf16), Some(*pos));
+            }
+            if tk.kind() == McTokenKind::QuotedString {
+                assert_eq!(index.line_col(tk.end()), Some(span.end()));
+                ends.push(index.to_utf16(span.end()).unwrap());
+            }
+        }
+        assert_eq!(ends, utf16_ends);
+    }
+
+    line_index_test!(
+        line_index_ascii,
+        "say \"hi\"\nsay \"there\"",
+        [LineCol::new(0, 8), LineCol::new(1, 11)]
+    );
+    line_index_test!(
+        line_index_two_byte,
+        "data merge entity @s {CustomName:\"\\\"Zoë\\\"\"}",
+        [LineCol::new(0, 42)]
+    );
+    line_index_test!(
+        line_index_surrogate_pair,
+        "tellraw @a {\"text\":\"🎉 ok\"}\r\nsay \"日本語\" \"𝄞\"",
+        [
+            LineCol::new(0, 18),
+            LineCol::new(0, 26),
+            LineCol::new(1, 9),
+            LineCol::new(1, 14)
+        ]
+    );
 }
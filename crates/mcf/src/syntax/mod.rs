@@ -1,7 +1,7 @@
 pub use group::McGroupType;
 use mcfunction_parse::{
     parser::{Language, Parser, StartInfo},
-    tokenset, Ast,
+    tokenset, Ast, LineCol, LineIndex, TextEdit, Trace,
 };
 pub use tokens::McTokenKind;
 use util::commands::{Command, CommandNodeType, Commands, Index, ParserType, StringType};
@@ -10,6 +10,8 @@ pub mod cst;
 pub mod grammar;
 pub mod lexer;
 pub mod tokens;
+pub mod validate;
+pub mod value;
 
 mod group;
 
@@ -46,15 +48,107 @@ impl<'c> CommandParser<'c> {
     }
 
     pub fn parse<'a>(&self, i: &'a str) -> Ast<&'a str, McfLang> {
+        self.parse_with(i, false).0
+    }
+
+    /// Parses `i` the same way as [`parse`], and records a [`Trace`] of how it was parsed.
+    ///
+    /// Besides the events recorded by the parser itself, the trace has a message
+    /// with the certainty of every argument that could come next in a command,
+    /// and with the literal or argument that was picked.
+    /// Every redirect that is followed gets a message as well.
+    /// The redirects that loop back are also found by [`Command::loop_backs`] on the tree itself.
+    ///
+    /// [`parse`]: #method.parse
+    /// [`Trace`]: ../../mcfunction_parse/struct.Trace.html
+    /// [`Command::loop_backs`]: cst/struct.Command.html#method.loop_backs
+    pub fn parse_traced<'a>(&self, i: &'a str) -> (Ast<&'a str, McfLang>, Trace<McfLang>) {
+        self.parse_with(i, true)
+    }
+
+    fn parse_with<'a>(&self, i: &'a str, trace: bool) -> (Ast<&'a str, McfLang>, Trace<McfLang>) {
         let tokens = lexer::tokenize_str(i);
         assert!(!tokens.is_empty(), "Token stream is empty");
         let mut p = Parser::new(&tokens[0], i, McGroupType::File, false);
+        if trace {
+            p.enable_trace();
+        }
         self.parse_line(&mut p);
         for line in &tokens[1..] {
             p.change_tokens(&line);
             self.parse_line(&mut p);
         }
-        p.build(true)
+        let trace = p.take_trace();
+        (p.build(true), trace)
+    }
+
+    /// Applies `edit` to the source of `ast`, only reparsing the lines that the edit touches.
+    ///
+    /// The resulting tree is the same as the one that [`parse`] would produce for the new source.
+    ///
+    /// [`parse`]: #method.parse
+    pub fn reparse<T: AsRef<str>>(
+        &self,
+        ast: Ast<T, McfLang>,
+        edit: &TextEdit,
+    ) -> Ast<String, McfLang> {
+        let src = edit.apply(ast.src());
+        let old_lines = LineIndex::new(ast.src());
+        let new_lines = LineIndex::new(&src);
+        let span = edit.span();
+        // An edit at the start of a line can turn a `\r` at the end of the previous line into `\r\n`
+        let first = if span.start().col() == 0 {
+            span.start().line().saturating_sub(1)
+        } else {
+            span.start().line()
+        };
+        let last_old = span.end().line();
+        let last_new = last_old + new_lines.line_count() - old_lines.line_count();
+
+        let start = new_lines.line_start(first).unwrap();
+        let old_end = old_lines
+            .line_start(last_old + 1)
+            .unwrap_or_else(|| ast.src().len());
+        let new_end = new_lines.line_start(last_new + 1).unwrap_or(src.len());
+
+        let lines: Vec<_> = ast
+            .root()
+            .children()
+            .map(|v| v.span().start().line())
+            .collect();
+        let range_start = lines.iter().take_while(|l| **l < first).count();
+        let range_end = range_start
+            + lines[range_start..]
+                .iter()
+                .take_while(|l| **l <= last_old)
+                .count();
+
+        let fragment = self.parse(&src[start..new_end]).retype_src::<String>();
+        ast.splice(
+            range_start..range_end,
+            fragment,
+            src,
+            (start, LineCol::new(first, 0)),
+            (old_end, LineCol::new(last_old + 1, 0)),
+            (new_end, LineCol::new(last_new + 1, 0)),
+        )
+    }
+
+    /// Applies `edits` one after another, reparsing only the lines that they touch.
+    ///
+    /// The edits returned by [`AstEditor::text_edits`] can be passed directly.
+    ///
+    /// [`AstEditor::text_edits`]: ../../mcfunction_parse/struct.AstEditor.html#method.text_edits
+    pub fn apply_edits<T: AsRef<str>>(
+        &self,
+        ast: Ast<T, McfLang>,
+        edits: &[TextEdit],
+    ) -> Ast<String, McfLang> {
+        let mut ast = ast.retype_src_with(|s| s.as_ref().to_owned());
+        for edit in edits {
+            ast = self.reparse(ast, edit);
+        }
+        ast
     }
 
     fn parse_line(&self, p: &mut McParser) {
@@ -68,18 +162,28 @@ impl<'c> CommandParser<'c> {
             p.finish(cmk);
         } else {
             let cmk = p.start(McGroupType::Command, StartInfo::None);
-            self.parse_command(self.commands.root(), self.commands.root_index(), p);
+            let root = self.commands.root_index();
+            self.parse_command(self.commands.root(), root, &mut vec![], p);
+            if !p.at(McTokenKind::Eof) {
+                let errmk = p.start(McGroupType::Error, StartInfo::None);
+                while !p.at(McTokenKind::Eof) {
+                    p.bump();
+                }
+                p.finish(errmk);
+            }
             p.finish(cmk);
         }
     }
 
-    fn parse_command(&self, c: &Command, ind: Index, p: &mut McParser) {
+    /// Parses the node `c` and everything after it, where `path` holds the nodes before it.
+    fn parse_command(&self, c: &Command, ind: Index, path: &mut Vec<Index>, p: &mut McParser) {
         if p.at(McTokenKind::Eof) {
             return;
         }
         match c.node_type() {
             CommandNodeType::Argument { parser_type } => {
                 let mk = p.start(McGroupType::CommandNode(ind), StartInfo::None);
+                p.push_recovery(tokenset![McTokenKind::Whitespace]);
                 match parser_type {
                     ParserType::BlockPos => grammar::coord::coord(p),
                     ParserType::BlockPredicate => grammar::block::predicate(p),
@@ -90,8 +194,9 @@ impl<'c> CommandParser<'c> {
                         }
                     }
                     ParserType::Color => {
-                        // TODO: Add keywords
-                        p.expect(McTokenKind::Word);
+                        if !p.expect_keyword(grammar::COLOR) && p.at(McTokenKind::Word) {
+                            p.bump();
+                        }
                     }
                     ParserType::ColumnPos => grammar::coord::coord2(p),
                     ParserType::Component => grammar::json::value(p),
@@ -105,7 +210,9 @@ impl<'c> CommandParser<'c> {
                     ParserType::Double | ParserType::Float { .. } => grammar::float(p),
                     ParserType::Entity { .. } => grammar::selector::entity(p),
                     ParserType::EntityAnchor => {
-                        p.expect(McTokenKind::Word);
+                        if !p.expect_keyword(grammar::ENTITY_ANCHOR) && p.at(McTokenKind::Word) {
+                            p.bump();
+                        }
                     }
                     ParserType::Function => grammar::function(p),
                     ParserType::GameProfile => grammar::selector::game_profile(p),
@@ -119,11 +226,9 @@ impl<'c> CommandParser<'c> {
                     ParserType::NbtPath => grammar::nbt::path(p),
                     ParserType::NbtTag => grammar::nbt::value(p),
                     ParserType::Objective => grammar::uq_string(p),
-                    ParserType::Operation => {
-                        p.eat_tokens(grammar::OPERATION);
-                    }
+                    ParserType::Operation => grammar::operation(p),
                     ParserType::Rotation => grammar::coord::coord2(p),
-                    ParserType::ScoreboardSlot => grammar::uq_string(p),
+                    ParserType::ScoreboardSlot => grammar::scoreboard_slot(p),
                     ParserType::ScoreHolder { .. } => grammar::selector::score_holder(p),
                     ParserType::String { properties } => match properties.string_type {
                         StringType::Word => grammar::uq_string(p),
@@ -131,13 +236,24 @@ impl<'c> CommandParser<'c> {
                         StringType::Greedy => grammar::message(p),
                     },
                     ParserType::Swizzle => {
-                        p.expect(McTokenKind::Word);
+                        if !p.expect_keyword(grammar::SWIZZLE) && p.at(McTokenKind::Word) {
+                            p.bump();
+                        }
                     }
                     ParserType::Team => grammar::uq_string(p),
                     ParserType::Time => grammar::time(p),
                     ParserType::Vec2 => grammar::coord::coord2(p),
                     ParserType::Vec3 => grammar::coord::coord(p),
                 }
+                p.pop_recovery();
+                // Skip the rest of a broken argument so that the next ones still parse,
+                // which only needs an error if the argument didn't report one already
+                if p.not_at(McTokenKind::Whitespace) {
+                    if !p.errored() {
+                        p.expect(McTokenKind::Whitespace);
+                    }
+                    p.recover(tokenset![McTokenKind::Whitespace]);
+                }
                 p.finish(mk);
             }
             CommandNodeType::Literal => {
@@ -156,28 +272,35 @@ impl<'c> CommandParser<'c> {
                 return;
             }
         }
+        path.push(ind);
+        let mut next = c;
+        let mut redirects = vec![ind];
+        while let Some(target) = next.redirect() {
+            if redirects.contains(&target) {
+                p.trace(|| "redirects go around in a circle".to_owned());
+                break;
+            }
+            redirects.push(target);
+            next = &self.commands[target];
+            p.trace(|| {
+                if target == self.commands.root_index() {
+                    "redirected to the root".to_owned()
+                } else if path.contains(&target) {
+                    format!("looped back to `{}`", next.name())
+                } else {
+                    format!("redirected to `{}`", next.name())
+                }
+            });
+        }
         let mut best: Option<(Certainty, &Command, Index)> = None;
-        for (index, child) in c.children(self.commands) {
+        for (index, child) in next.children(self.commands) {
+            // Checking a child doesn't consume anything, so a node with many children
+            // would otherwise run out of fuel before reaching the last one
+            p.refuel();
             match child.node_type() {
-                CommandNodeType::Root => {
-                    self.parse_command(self.commands.root(), index, p);
-                    return;
-                }
-                CommandNodeType::Literal => {
-                    if p.at_keyword(&[(child.name(), McGroupType::Error)]) {
-                        self.parse_command(child, index, p);
-                        return;
-                    } else {
-                        for (n, tk) in lexer::PUNCT {
-                            if child.name() == *n && p.at(*tk) {
-                                self.parse_command(child, index, p);
-                                return;
-                            }
-                        }
-                    }
-                }
                 CommandNodeType::Argument { parser_type } => {
                     let cty = parser_lookahead(p, parser_type);
+                    p.trace(|| format!("argument <{}> is {:?}", child.name(), cty));
                     if let Some((c, cmd, _)) = best {
                         if cty > c
                             || (cmd.node_type() == child.node_type()
@@ -189,11 +312,28 @@ impl<'c> CommandParser<'c> {
                         best = Some((cty, child, index));
                     }
                 }
+                // The root is never a child, so anything that isn't an argument is a literal
+                _ => {
+                    if p.at_keyword(&[(child.name(), McGroupType::Error)]) {
+                        p.trace(|| format!("picked literal `{}`", child.name()));
+                        self.parse_command(child, index, path, p);
+                        return;
+                    } else {
+                        for (n, tk) in lexer::PUNCT {
+                            if child.name() == *n && p.at(*tk) {
+                                p.trace(|| format!("picked literal `{}`", child.name()));
+                                self.parse_command(child, index, path, p);
+                                return;
+                            }
+                        }
+                    }
+                }
             }
         }
         if let Some((_, child, index)) = best {
             if !p.at(McTokenKind::Eof) {
-                self.parse_command(child, index, p);
+                p.trace(|| format!("picked argument <{}>", child.name()));
+                self.parse_command(child, index, path, p);
             }
         } else {
             let errmk = p.start(McGroupType::Error, StartInfo::None);
@@ -238,8 +378,10 @@ fn parser_lookahead(p: &McParser, arg: ParserType) -> Certainty {
             }
         }
         ParserType::Color => {
-            if p.at(McTokenKind::Word) {
-                return Certainty::Probably;
+            if p.at_keyword(grammar::COLOR) {
+                return Certainty::Yes;
+            } else if p.at(McTokenKind::Word) {
+                return Certainty::Maybe;
             }
         }
         ParserType::Component => {
@@ -267,7 +409,7 @@ fn parser_lookahead(p: &McParser, arg: ParserType) -> Certainty {
             }
         }
         ParserType::EntityAnchor => {
-            if p.at_keyword(&[("eyes", McGroupType::Error), ("feet", McGroupType::Error)]) {
+            if p.at_keyword(grammar::ENTITY_ANCHOR) {
                 return Certainty::Yes;
             } else if p.at(McTokenKind::Word) {
                 return Certainty::Maybe;
@@ -340,11 +482,9 @@ fn parser_lookahead(p: &McParser, arg: ParserType) -> Certainty {
             }
         }
         ParserType::ScoreboardSlot => {
-            if p.at_tokens(tokenset![
-                McTokenKind::Dot,
-                McTokenKind::DotDot,
-                McTokenKind::Word
-            ]) {
+            if p.at_token(grammar::scoreboard_slot_tk) {
+                return Certainty::Yes;
+            } else if p.at(McTokenKind::Word) {
                 return Certainty::Maybe;
             }
         }
@@ -378,7 +518,9 @@ fn parser_lookahead(p: &McParser, arg: ParserType) -> Certainty {
             }
         },
         ParserType::Swizzle => {
-            if p.at(McTokenKind::Word) {
+            if p.at_keyword(grammar::SWIZZLE) {
+                return Certainty::Yes;
+            } else if p.at(McTokenKind::Word) {
                 return Certainty::Maybe;
             }
         }
@@ -403,10 +545,967 @@ fn parser_lookahead(p: &McParser, arg: ParserType) -> Certainty {
     Certainty::No
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum Certainty {
     No,
     Maybe,
     Probably,
     Yes,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cst::{self, CoordValue, NbtSequenceType, SelectorKind},
+        testing::{commands, format_astnode},
+        CommandParser, McGroupType, McTokenKind, McfLang,
+    };
+    use insta::assert_snapshot;
+    use mcfunction_parse::{
+        ast::{CstNode, TokenAtOffset, Visit, Visitor, WalkEvent},
+        parser::Progress,
+        Ast, AstEditor, AstView, LineCol, Span, SyntaxKind, TextEdit, TraceEvent,
+    };
+    use std::sync::Arc;
+    use util::commands::ParserType;
+
+    macro_rules! reparse_test {
+        ($name:ident, $src:expr, $edit:expr) => {
+            #[test]
+            fn $name() {
+                reparse($src, &[$edit]);
+            }
+        };
+    }
+
+    fn reparse(src: &str, edits: &[TextEdit]) {
+        let cmds = commands();
+        let p = CommandParser::new(&cmds);
+        let mut src = String::from(src);
+        let mut ast = p.parse(&src).retype_src::<String>();
+        for edit in edits {
+            src = edit.apply(&src);
+            ast = p.reparse(ast, edit);
+            let full = p.parse(&src);
+            assert_eq!(ast.src(), src);
+            assert_eq!(
+                format_astnode(ast.root(), 0),
+                format_astnode(full.root(), 0)
+            );
+            assert_eq!(
+                ast.errors().map(|v| v.span()).collect::<Vec<_>>(),
+                full.errors().map(|v| v.span()).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    fn edit(sl: usize, sc: usize, el: usize, ec: usize, text: &str) -> TextEdit {
+        TextEdit::new(Span::new(LineCol::new(sl, sc), LineCol::new(el, ec)), text)
+    }
+
+    reparse_test!(
+        change_word,
+        "say hello\nsay world\n",
+        edit(0, 4, 0, 9, "goodbye")
+    );
+    reparse_test!(
+        insert_line,
+        "say one\nsay three",
+        edit(1, 0, 1, 0, "say two\n")
+    );
+    reparse_test!(
+        delete_line,
+        "say one\nsay two\nsay three",
+        edit(1, 0, 2, 0, "")
+    );
+    reparse_test!(
+        join_lines,
+        "say one\n# comment\nsay three",
+        edit(0, 7, 1, 0, " ")
+    );
+    reparse_test!(
+        split_line,
+        "say one two\nsay three",
+        edit(0, 7, 0, 8, "\nsay ")
+    );
+    reparse_test!(
+        complete_crlf,
+        "say one\rsay two\nsay three",
+        edit(1, 0, 1, 0, "\n")
+    );
+    reparse_test!(
+        append_end,
+        "say one\nsay two",
+        edit(1, 7, 1, 7, "\ngive @a diamond{a:1b} 5")
+    );
+    reparse_test!(
+        introduce_error,
+        "give @a diamond 5\nsay two\n",
+        edit(0, 16, 0, 17, "[")
+    );
+    reparse_test!(
+        multi_line_replace,
+        "# a\nsay b\nsay c\n\nsay d\n# e",
+        edit(1, 2, 4, 3, "ve @s stone\n\n# x\nsay")
+    );
+
+    macro_rules! lossless_test {
+        ($name:ident, $src:expr) => {
+            #[test]
+            fn $name() {
+                let cmds = commands();
+                assert_eq!(
+                    CommandParser::new(&cmds).parse($src).root().to_string(),
+                    $src
+                );
+            }
+        };
+    }
+
+    lossless_test!(blank_lines, "say hi\n\n\n# comment\n");
+    lossless_test!(line_endings, "say a\r\nsay b\rsay c\n");
+    lossless_test!(trailing_whitespace, "tp @s ~ ~ ~  \t\n  ");
+    lossless_test!(unknown_command, "  hello there @e[type=pig]");
+    lossless_test!(leftover_tokens, "give @a diamond{a:1}x 5 6 7");
+    lossless_test!(unclosed_nbt, "give @a diamond{a:[1, 2 \nsay ok");
+    lossless_test!(block_state_garbage, "setblock 1 2 3 stone[[a=b c=d\nsay ok");
+    lossless_test!(selector_garbage, "give @a[[type=pig,scores={[x=1..2] stone");
+    lossless_test!(score_holder_eof, "scoreboard players set foo");
+    lossless_test!(nbt_sequence_eof, "data get entity @s a{b:[");
+    lossless_test!(nbt_bad_separator, "give @a diamond{a:[1 ; }, b:2} 5");
+
+    #[test]
+    #[should_panic(
+        expected = "Parser did not make progress in the loop at crates/mcf/src/syntax/mod.rs"
+    )]
+    fn no_progress() {
+        super::parse_single("say", |p| {
+            p.set_strict(true);
+            let mut progress = Progress::new();
+            while p.progressed(&mut progress) {
+                p.eat(McTokenKind::Comma);
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Parser is stuck at crates/mcf/src/syntax/mod.rs")]
+    fn out_of_fuel() {
+        super::parse_single("say", |p| {
+            p.set_strict(true);
+            while !p.at(McTokenKind::Comma) {}
+        });
+    }
+
+    #[test]
+    fn no_progress_recovers() {
+        let ast = super::parse_single("say hi", |p| {
+            p.set_strict(false);
+            let mut progress = Progress::new();
+            while p.progressed(&mut progress) {
+                p.eat(McTokenKind::Comma);
+            }
+        });
+        assert_eq!(ast.root().to_string(), "say hi");
+        assert_eq!(ast.errors().count(), 3);
+        assert!(ast.errors().all(|e| matches!(
+            e.kind(),
+            SyntaxKind::Error(mcfunction_parse::ParseError::Unexpected)
+        )));
+    }
+
+    #[test]
+    fn out_of_fuel_recovers() {
+        let ast = super::parse_single("say hi", |p| {
+            p.set_strict(false);
+            while p.not_at(McTokenKind::Comma) {}
+        });
+        assert_eq!(ast.root().to_string(), "say hi");
+        assert_eq!(ast.errors().count(), 1);
+    }
+
+    #[test]
+    fn many_literal_siblings() {
+        let children: Vec<_> = (0..300)
+            .map(|i| format!(r#""c{}": {{"type": "literal", "executable": true}}"#, i))
+            .collect();
+        let json = format!(
+            r#"{{"type": "root", "children": {{{}}}}}"#,
+            children.join(",")
+        );
+        let cmds = util::commands::Commands::generate(serde_json::from_str(&json).unwrap());
+        let ast = CommandParser::new(&cmds).parse("c199\nc299");
+        assert_eq!(ast.errors().count(), 0);
+    }
+
+    macro_rules! diagnostic_test {
+        ($name:ident, $src:expr) => {
+            #[test]
+            fn $name() {
+                let cmds = commands();
+                let ast = CommandParser::new(&cmds).parse($src);
+                let out = ast
+                    .diagnostics()
+                    .iter()
+                    .map(|d| d.display("test.mcfunction", $src).to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                assert_snapshot!(out);
+            }
+        };
+    }
+
+    diagnostic_test!(diagnostic_bad_argument, "give @a diamond five");
+    diagnostic_test!(diagnostic_selector, "say hi\ngive @a[type=pig diamond\n");
+    diagnostic_test!(diagnostic_unclosed_nbt, "give @a diamond{a:[1, 2 \nsay ok");
+    diagnostic_test!(
+        diagnostic_keywords,
+        "execute align xx run say hi\nteam modify a color pink\nscoreboard objectives setdisplay sidebar.team.reset"
+    );
+    diagnostic_test!(
+        diagnostic_recover_argument,
+        "give @a diamond{a:1b;b:2} five"
+    );
+
+    #[test]
+    fn recover_after_bad_argument() {
+        let cmds = commands();
+        let src = "give @a[type=pig x,limit=1] diamond{a:1b x,b:2} 5";
+        let ast = CommandParser::new(&cmds).parse(src);
+        let span = |sc, ec| Span::new(LineCol::new(0, sc), LineCol::new(0, ec));
+
+        // Each list skips to its next separator, so the later entries still parse
+        let node = ast.covering_element(span(19, 24));
+        assert_eq!(node.string(), "limit");
+        assert!(node
+            .ancestors()
+            .any(|v| v.kind() == &SyntaxKind::Group(McGroupType::SelectorArgument)));
+        let node = ast.covering_element(span(43, 46));
+        assert_eq!(
+            node.kind(),
+            &SyntaxKind::Group(McGroupType::NbtCompoundEntry)
+        );
+        assert_eq!(node.string(), "b:2");
+
+        // The count after the item is parsed as its own argument
+        let node = ast
+            .covering_element(span(48, 49))
+            .ancestors()
+            .find(|v| matches!(v.kind(), SyntaxKind::Group(McGroupType::CommandNode(_))))
+            .unwrap();
+        assert_eq!(node.string(), "5");
+        assert_eq!(ast.errors().count(), 2);
+    }
+
+    macro_rules! sexp_test {
+        ($name:ident, $src:expr) => {
+            #[test]
+            fn $name() {
+                let cmds = commands();
+                let ast = CommandParser::new(&cmds).parse($src);
+                assert_snapshot!(ast.root().sexp().to_string());
+            }
+        };
+    }
+
+    sexp_test!(sexp_simple, "say hi\n");
+    sexp_test!(sexp_error, "give @a diamond five");
+    sexp_test!(
+        sexp_keywords,
+        "team modify a color dark_red\n\
+         execute align zx anchored eyes run say hi\n\
+         scoreboard objectives setdisplay sidebar.team.red\n\
+         scoreboard players operation a b >< c d\n"
+    );
+
+    fn json_text(node: &serde_json::Value, out: &mut String) {
+        if let Some(text) = node.get("text") {
+            out.push_str(text.as_str().unwrap());
+        }
+        for child in node["children"].as_array().into_iter().flatten() {
+            json_text(child, out);
+        }
+    }
+
+    #[test]
+    fn json() {
+        let cmds = commands();
+        let src = "say hi\ngive @a diamond five";
+        let ast = CommandParser::new(&cmds).parse(src);
+        let json = serde_json::to_value(&ast).unwrap();
+
+        let root = &json["root"];
+        assert_eq!(root["kind"], "root");
+        assert_eq!(root["type"], "File");
+        assert_eq!(root["children"][0]["children"][0]["type"], "CommandNode");
+        assert_eq!(root["range"], serde_json::json!([0, src.len()]));
+        assert_eq!(
+            root["children"][0]["span"],
+            serde_json::json!({
+                "start": { "line": 0, "col": 0 },
+                "end": { "line": 0, "col": 6 },
+            })
+        );
+
+        let mut text = String::new();
+        json_text(root, &mut text);
+        assert_eq!(text, src);
+
+        let errors = json["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), ast.errors().count());
+        assert_eq!(errors[0]["message"], ast.diagnostics()[0].message());
+    }
+
+    #[test]
+    fn trace_lookahead() {
+        let src = r#"{"a": [1, nul]}"#;
+        let tokens = super::lexer::tokenize_str(src);
+        let mut p = super::Parser::new(&tokens[0], src, McGroupType::File, false);
+        p.enable_trace();
+        super::grammar::json::value(&mut p);
+        assert_snapshot!(p.take_trace().to_string());
+    }
+
+    #[test]
+    fn trace_certainty() {
+        let cmds = commands();
+        let (ast, trace) = CommandParser::new(&cmds).parse_traced("tp @s ~ ~1 ~");
+        assert_eq!(ast.root().to_string(), "tp @s ~ ~1 ~");
+        let mut messages: Vec<_> = trace.messages().collect();
+        messages.sort_unstable();
+        assert_eq!(
+            messages,
+            vec![
+                "argument <destination> is Yes",
+                "argument <location> is No",
+                "picked argument <destination>",
+                "picked literal `tp`",
+                "redirected to `teleport`",
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_redirects() {
+        let cmds = commands();
+        let root = cmds.root();
+        let (tp, tp_cmd) = root
+            .children(&cmds)
+            .find(|(_, c)| c.name() == "tp")
+            .unwrap();
+        let (teleport, _) = root
+            .children(&cmds)
+            .find(|(_, c)| c.name() == "teleport")
+            .unwrap();
+        assert_eq!(tp_cmd.redirect(), Some(teleport));
+        assert!(tp_cmd.children_indices().is_empty());
+        assert_ne!(tp, teleport);
+
+        let src = "execute as @a at @s run say hi";
+        let (ast, trace) = CommandParser::new(&cmds).parse_traced(src);
+        assert_eq!(ast.diagnostics(), vec![]);
+        let redirects: Vec<_> = trace
+            .messages()
+            .filter(|m| m.starts_with("redirected") || m.starts_with("looped"))
+            .collect();
+        assert_eq!(
+            redirects,
+            vec![
+                "looped back to `execute`",
+                "looped back to `execute`",
+                "redirected to the root",
+            ]
+        );
+        assert!(CommandParser::new(&cmds)
+            .parse_traced("tp @s")
+            .1
+            .events()
+            .iter()
+            .any(|evt| matches!(evt, TraceEvent::Bump { token } if token.text() == "@")));
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn loop_backs() {
+        let cmds = commands();
+        let src = "execute as @a at @s run say hi
+execute as @a
+tp @s ~ ~ ~
+say hi";
+        let ast = CommandParser::new(&cmds).parse(src);
+        let file: cst::File<cst::Node> =
+            Ast::cst_root(Arc::new(ast.retype_src::<Arc<str>>())).unwrap();
+        let loops: Vec<_> = file
+            .lines()
+            .filter_map(|l| l.command())
+            .map(|c| {
+                c.loop_backs(&cmds)
+                    .into_iter()
+                    .map(|(node, target)| {
+                        format!("{} -> {}", node.view().span(), cmds[target].name())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            loops,
+            vec![
+                vec!["0:11 - 0:13 -> execute", "0:17 - 0:19 -> execute"],
+                vec![],
+                vec![],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_order() {
+        let cmds = commands();
+        let ast = CommandParser::new(&cmds).parse("say hi\n# c");
+        let mut out = String::new();
+        for evt in ast.root().walk() {
+            match evt {
+                WalkEvent::Enter(node) => match node.kind() {
+                    SyntaxKind::Token(_) => out.push_str(&format!("{:?} ", node.string())),
+                    SyntaxKind::Group(McGroupType::CommandNode(_)) => out.push_str("Node( "),
+                    SyntaxKind::Root(g) | SyntaxKind::Group(g) | SyntaxKind::Joined(g) => {
+                        out.push_str(&format!("{:?}( ", g))
+                    }
+                    SyntaxKind::Error(_) => out.push_str("Error "),
+                },
+                WalkEvent::Leave(node) => {
+                    if let SyntaxKind::Token(_) = node.kind() {
+                    } else {
+                        out.push_str(") ");
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            out,
+            "File( Command( Node( \"say\" ) \" \" Node( UnquotedString( \"hi\" ) ) ) \
+             \"\\n\" Comment( \"#\" UnquotedString( \" \" \"c\" ) ) ) "
+        );
+    }
+
+    #[derive(Default)]
+    struct TokenCounter {
+        tokens: usize,
+        selectors: usize,
+    }
+
+    impl<'a> Visitor<'a, &'a str, McfLang> for TokenCounter {
+        fn visit_group(&mut self, _: AstView<'a, &'a str, McfLang>, kind: McGroupType) -> Visit {
+            if kind == McGroupType::Selector {
+                self.selectors += 1;
+                Visit::SkipChildren
+            } else {
+                Visit::Continue
+            }
+        }
+
+        fn visit_token(&mut self, _: AstView<'a, &'a str, McfLang>, _: McTokenKind) -> Visit {
+            self.tokens += 1;
+            Visit::Continue
+        }
+    }
+
+    #[test]
+    fn visitor_skip_children() {
+        let cmds = commands();
+        let ast = CommandParser::new(&cmds).parse("give @a[type=pig] diamond\ngive @s stone");
+        let mut counter = TokenCounter::default();
+        ast.root().visit(&mut counter);
+        assert_eq!(counter.selectors, 2);
+        // `give`, the whitespace and the item of each line, plus the line break
+        assert_eq!(counter.tokens, 9);
+    }
+
+    fn token_at<'a>(
+        ast: &'a Ast<&str, McfLang>,
+        line: usize,
+        col: usize,
+    ) -> TokenAtOffset<&'a str> {
+        ast.token_at_offset(LineCol::new(line, col))
+            .map(|v| v.string())
+    }
+
+    #[test]
+    fn token_at_offset() {
+        let cmds = commands();
+        let ast = CommandParser::new(&cmds).parse("give @a[type=pig] diamond\nsay hi");
+        assert_eq!(token_at(&ast, 0, 0), TokenAtOffset::Single("give"));
+        assert_eq!(token_at(&ast, 0, 2), TokenAtOffset::Single("give"));
+        assert_eq!(token_at(&ast, 0, 4), TokenAtOffset::Between("give", " "));
+        assert_eq!(token_at(&ast, 0, 8), TokenAtOffset::Between("[", "type"));
+        assert_eq!(
+            token_at(&ast, 0, 25),
+            TokenAtOffset::Between("diamond", "\n")
+        );
+        assert_eq!(token_at(&ast, 1, 0), TokenAtOffset::Between("\n", "say"));
+        assert_eq!(token_at(&ast, 1, 6), TokenAtOffset::Single("hi"));
+        assert_eq!(token_at(&ast, 1, 7), TokenAtOffset::None);
+        assert_eq!(token_at(&ast, 2, 0), TokenAtOffset::None);
+        assert_eq!(token_at(&ast, 0, 4).left_biased(), Some("give"));
+        assert_eq!(token_at(&ast, 0, 4).right_biased(), Some(" "));
+    }
+
+    #[test]
+    fn token_at_offset_errors() {
+        let cmds = commands();
+        let ast = CommandParser::new(&cmds).parse("give @a[type=pig diamond");
+        // There are zero width errors at the end of the line
+        assert!(ast
+            .errors()
+            .any(|e| e.span().start() == LineCol::new(0, 24)));
+        assert_eq!(token_at(&ast, 0, 24), TokenAtOffset::Single("diamond"));
+        assert_eq!(token_at(&ast, 0, 16), TokenAtOffset::Between("pig", " "));
+    }
+
+    #[test]
+    fn covering_element() {
+        let cmds = commands();
+        let ast = CommandParser::new(&cmds).parse("give @a[type=pig] diamond\nsay hi");
+        let span = |sl, sc, el, ec| Span::new(LineCol::new(sl, sc), LineCol::new(el, ec));
+
+        let node = ast.covering_element(span(0, 9, 0, 11));
+        assert_eq!(node.kind(), &SyntaxKind::Token(McTokenKind::Word));
+        assert_eq!(node.string(), "type");
+        assert!(node
+            .ancestors()
+            .any(|v| v.kind() == &SyntaxKind::Group(McGroupType::Selector)));
+        assert_eq!(node.ancestors().next(), Some(node));
+        assert_eq!(node.ancestors().last(), Some(ast.root()));
+
+        // An empty span between two tokens is covered by their common parent
+        let node = ast.covering_element(span(0, 8, 0, 8));
+        assert_eq!(
+            node.kind(),
+            &SyntaxKind::Group(McGroupType::SelectorArgument)
+        );
+
+        let node = ast.covering_element(span(0, 5, 0, 25));
+        assert_eq!(node.kind(), &SyntaxKind::Group(McGroupType::Command));
+        assert_eq!(node.string(), "give @a[type=pig] diamond");
+
+        let node = ast.covering_element(span(0, 20, 1, 2));
+        assert_eq!(node, ast.root());
+    }
+
+    fn find<T: AsRef<str>>(
+        ast: &Ast<T, McfLang>,
+        group: McGroupType,
+    ) -> Vec<AstView<'_, T, McfLang>> {
+        ast.root()
+            .walk()
+            .filter_map(|evt| match evt {
+                WalkEvent::Enter(node) if node.kind() == &SyntaxKind::Group(group) => Some(node),
+                _ => None,
+            })
+            .collect()
+    }
+
+    macro_rules! editor_test {
+        ($name:ident, $src:expr, |$ast:ident, $editor:ident| $body:block, $res:expr, $edits:expr) => {
+            #[test]
+            fn $name() {
+                let cmds = commands();
+                let p = CommandParser::new(&cmds);
+                let $ast = p.parse($src);
+                let (res, edits) = {
+                    let mut $editor = AstEditor::new(&$ast);
+                    $body($editor.apply(), $editor.text_edits())
+                };
+                assert_eq!(res, $res);
+                assert_eq!(edits, $edits);
+                let ast = p.apply_edits($ast, &edits);
+                assert_eq!(ast.src(), res);
+                assert_eq!(
+                    format_astnode(ast.root(), 0),
+                    format_astnode(p.parse(&res).root(), 0)
+                );
+            }
+        };
+    }
+
+    editor_test!(
+        editor_rename_function,
+        "function foo:bar\nexecute as @a run function foo:bar\nfunction foo:other",
+        |ast, editor| {
+            for node in find(&ast, McGroupType::Function) {
+                if node.string() == "foo:bar" {
+                    editor.replace(node, "foo:baz");
+                }
+            }
+        },
+        "function foo:baz\nexecute as @a run function foo:baz\nfunction foo:other",
+        vec![edit(1, 33, 1, 34, "z"), edit(0, 15, 0, 16, "z")]
+    );
+    editor_test!(
+        editor_replace_compound,
+        "give @a diamond{a:1b} 5",
+        |ast, editor| {
+            editor.replace(find(&ast, McGroupType::NbtCompound)[0], "{a:2b}");
+        },
+        "give @a diamond{a:2b} 5",
+        vec![edit(0, 18, 0, 19, "2")]
+    );
+    editor_test!(
+        editor_join_touching,
+        "say hi\nsay bye",
+        |ast, editor| {
+            let cmd = find(&ast, McGroupType::Command)[0];
+            editor.delete(cmd);
+            editor.insert_after(cmd, "say hello");
+        },
+        "say hello\nsay bye",
+        vec![edit(0, 5, 0, 6, "ello")]
+    );
+    editor_test!(
+        editor_insert_and_delete,
+        "say a\nsay b\nsay c",
+        |ast, editor| {
+            let cmds = find(&ast, McGroupType::Command);
+            editor.insert_before(cmds[0], "# start\n");
+            editor.insert_before(cmds[0], "# second\n");
+            editor.delete(cmds[1]);
+            editor.replace(cmds[2], "say c");
+        },
+        "# start\n# second\nsay a\n\nsay c",
+        vec![
+            edit(1, 0, 1, 5, ""),
+            edit(0, 0, 0, 0, "# start\n# second\n")
+        ]
+    );
+
+    #[test]
+    #[should_panic(expected = "Edits must not overlap")]
+    fn editor_overlap() {
+        let cmds = commands();
+        let ast = CommandParser::new(&cmds).parse("give @a diamond{a:1b} 5");
+        let mut editor = AstEditor::new(&ast);
+        editor.replace(find(&ast, McGroupType::NbtCompound)[0], "{}");
+        editor.insert_before(find(&ast, McGroupType::NbtCompoundEntry)[0], "b:1b,");
+    }
+
+    #[test]
+    fn repeated_edits() {
+        let mut edits = vec![];
+        for i in 0..30 {
+            let text = if i % 2 == 0 { "bye" } else { "say" };
+            edits.push(edit(i % 3, 0, i % 3, 3, text));
+        }
+        reparse("say one\nsay two\nsay three\n", &edits);
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn cst_nodes() {
+        let cmds = commands();
+        let src = "say hi\n# c\n\ngive @a diamond{a: 1b, b: [1, 2]} 5\n";
+        let ast = Arc::new(
+            CommandParser::new(&cmds)
+                .parse(src)
+                .retype_src::<Arc<str>>(),
+        );
+
+        // Children of other types, such as the line breaks, are skipped
+        let file: cst::File<cst::Node> = Ast::cst_root(ast.clone()).unwrap();
+        let lines = file.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].as_ref().comment().is_some());
+        assert!(lines[2].as_ref().command().is_some());
+
+        let ind = find(&ast, McGroupType::NbtCompound)[0].index();
+        let compound: cst::NbtCompound<cst::Node> = Ast::cst_index(ast.clone(), ind).unwrap();
+        let entries = compound.entries().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].key().unwrap().view().string(), "a");
+        let byte = entries[0].value().unwrap().number().unwrap();
+        assert_eq!(byte.byte().unwrap().string(), "1");
+        let seq = entries[1].value().unwrap().sequence().unwrap();
+        assert_eq!(seq.seq_type(), NbtSequenceType::List);
+        assert_eq!(seq.entries().count(), 2);
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn cst_arguments() {
+        let cmds = commands();
+        let src = "setblock ~ ^-1.5 3 oak_stairs[facing=east,half=]{a:1b}\n\
+                   give @e[type=!pig,scores={a=1..}] minecraft:diamond_sword{Damage:1} 5\n\
+                   give Steve stone";
+        let ast = Arc::new(
+            CommandParser::new(&cmds)
+                .parse(src)
+                .retype_src::<Arc<str>>(),
+        );
+        let file: cst::File<cst::Node> = Ast::cst_root(ast).unwrap();
+        let commands = file.lines().filter_map(|l| l.command()).collect::<Vec<_>>();
+        assert_eq!(commands.len(), 3);
+
+        let nodes = commands[0].nodes().collect::<Vec<_>>();
+        assert!(nodes[0].child_type::<cst::Literal<_>>(&cmds).is_some());
+        assert!(nodes[1].child_type::<cst::Literal<_>>(&cmds).is_none());
+        let coord: cst::Coord<_> = nodes[1].child_type(&cmds).unwrap();
+        assert_eq!(
+            coord.parts().map(|p| p.value()).collect::<Vec<_>>(),
+            vec![
+                Some(CoordValue::Relative(0.0)),
+                Some(CoordValue::Local(-1.5)),
+                Some(CoordValue::Absolute(3.0))
+            ]
+        );
+        let block: cst::BlockState<_> = nodes[2].child_type(&cmds).unwrap();
+        assert!(!block.is_tag());
+        assert_eq!(block.id().unwrap().path(), "oak_stairs");
+        let props = block
+            .properties()
+            .map(|p| {
+                let key = p.key().unwrap().string().to_owned();
+                (key, p.value().map(|v| v.string().to_owned()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            props,
+            vec![
+                ("facing".to_owned(), Some("east".to_owned())),
+                ("half".to_owned(), None)
+            ]
+        );
+        assert_eq!(block.nbt().unwrap().entries().count(), 1);
+
+        let nodes = commands[1].nodes().collect::<Vec<_>>();
+        let selector: cst::Selector<_> = nodes[1].child_type(&cmds).unwrap();
+        assert_eq!(selector.kind(), Some(SelectorKind::AllEntities));
+        let args = selector.arguments().collect::<Vec<_>>();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0].key().unwrap().string(), "type");
+        assert!(args[0].inverted());
+        let id = args[0].value().unwrap().resource_location().unwrap();
+        assert_eq!((id.namespace(), id.path()), ("minecraft", "pig"));
+        let map = args[1].value().unwrap().map().unwrap();
+        let entry = map.entries().next().unwrap();
+        assert_eq!(entry.key().unwrap(), "a");
+        assert!(!entry.inverted());
+        assert!(entry.value().unwrap().range().is_some());
+        let item: cst::ItemStack<_> = nodes[2].child_type(&cmds).unwrap();
+        assert_eq!(item.id().unwrap().path(), "diamond_sword");
+        assert!(item.nbt().is_some());
+        let count: cst::Integer<_> = nodes[3].child_type(&cmds).unwrap();
+        assert_eq!(count.value(), Some(5));
+        assert!(nodes[3].child_type::<cst::FloatToken<_>>(&cmds).is_none());
+
+        let nodes = commands[2].nodes().collect::<Vec<_>>();
+        let player: cst::Selector<_> = nodes[1].child_type(&cmds).unwrap();
+        assert_eq!(player.kind(), None);
+        assert_eq!(player.name().unwrap().string(), "Steve");
+        assert_eq!(player.arguments().count(), 0);
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn cst_values() {
+        fn cast<C: CstNode<String = Arc<str>, Language = McfLang, Node = cst::Node>>(
+            src: &str,
+            f: fn(&mut super::McParser),
+            group: McGroupType,
+        ) -> C {
+            let ast = Arc::new(super::parse_single(src, f).retype_src::<Arc<str>>());
+            let ind = find(&ast, group)[0].index();
+            Ast::cst_index(ast.clone(), ind).unwrap()
+        }
+
+        let sel: cst::Selector<_> = cast(
+            "1-2-3-4-5",
+            super::grammar::selector::game_profile,
+            McGroupType::Selector,
+        );
+        assert_eq!(
+            sel.uuid().unwrap().value(),
+            Some(1 << 96 | 2 << 80 | 3 << 64 | 4 << 48 | 5)
+        );
+        let time: cst::Time<_> = cast("2d", super::grammar::time, McGroupType::Time);
+        assert_eq!(time.ticks(), Some(48000));
+        let time: cst::Time<_> = cast("15", super::grammar::time, McGroupType::Time);
+        assert_eq!(time.ticks(), Some(15));
+
+        let path: cst::NbtPath<_> = cast(
+            "Items[{Slot:1b}].tag.\"a b\"[0]",
+            super::grammar::nbt::path,
+            McGroupType::NbtPath,
+        );
+        let segments = path.segments().collect::<Vec<_>>();
+        assert_eq!(segments.len(), 5);
+        assert_eq!(segments[0].name(), Some("Items"));
+        assert!(segments[1].index().unwrap().filter().is_some());
+        assert_eq!(segments[3].name(), Some("\"a b\""));
+        let index = segments[4].index().unwrap();
+        assert_eq!(index.position().unwrap().value(), Some(0));
+
+        let json: cst::JsonObject<_> = cast(
+            "{\"a\": [1.5, true], \"b\":",
+            super::grammar::json::value,
+            McGroupType::JsonObject,
+        );
+        let entries = json.entries().collect::<Vec<_>>();
+        assert_eq!(entries[0].key(), Some("\"a\""));
+        let array = entries[0].value().unwrap().array().unwrap();
+        let values = array.values().collect::<Vec<_>>();
+        assert_eq!(values[0].as_ref().number().unwrap().value(), Some(1.5));
+        assert!(matches!(values[1], cst::JsonValue::True(_)));
+        assert_eq!(entries[1].key(), Some("\"b\""));
+        assert!(entries[1].value().is_none());
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn cst_keywords() {
+        let cmds = commands();
+        let src = "execute align xz anchored feet run scoreboard players operation a b %= c d";
+        let ast = Arc::new(
+            CommandParser::new(&cmds)
+                .parse(src)
+                .retype_src::<Arc<str>>(),
+        );
+        let file: cst::File<cst::Node> = Ast::cst_root(ast).unwrap();
+        let command = file.lines().find_map(|l| l.command()).unwrap();
+        let nodes = command.nodes().collect::<Vec<_>>();
+        let swizzle: cst::Swizzle<_> = nodes[2].child_type(&cmds).unwrap();
+        assert_eq!(swizzle.view().string(), "xz");
+        let anchor: cst::EntityAnchor<_> = nodes[4].child_type(&cmds).unwrap();
+        assert_eq!(anchor.view().string(), "feet");
+        let operation: cst::Operation<_> = nodes[11].child_type(&cmds).unwrap();
+        assert!(matches!(
+            operation.operator(),
+            Some(cst::Operator::ModAssign(_))
+        ));
+    }
+
+    #[test]
+    fn usage() {
+        let cmds = commands();
+        assert_eq!(
+            cmds.help(),
+            vec![
+                "/clear <targets> [<item>]",
+                "/effect give <targets> <effect> [<seconds>]",
+                "/execute (align|anchored|as|at|if|run)",
+                "/function <name>",
+                "/give <targets> <item> [<count>]",
+                "/op <targets>",
+                "/playsound <pitch>",
+                "/say <message>",
+                "/scoreboard (objectives|players)",
+                "/setblock <pos> <block>",
+                "/team modify <team> color <value>",
+                "/teleport (<destination>|<location>)",
+                "/tp -> teleport",
+            ]
+        );
+
+        let find = |name| {
+            cmds.root()
+                .children(&cmds)
+                .find(|(_, c)| c.name() == name)
+                .unwrap()
+                .0
+        };
+        assert_eq!(
+            cmds.all_usage(find("give")),
+            vec!["<targets> <item>", "<targets> <item> <count>"]
+        );
+        let execute = cmds.all_usage(find("execute"));
+        assert!(execute.contains(&"as <targets> -> execute".to_owned()));
+        assert!(execute.contains(&"if block <pos> <block>".to_owned()));
+        assert!(execute.contains(&"run ...".to_owned()));
+        let smart = cmds
+            .smart_usage(find("execute"))
+            .into_iter()
+            .map(|(_, usage)| usage)
+            .collect::<Vec<_>>();
+        assert_eq!(smart[4], "if (block|score)");
+
+        // Literals come before arguments, no matter their names
+        let json = r#"{"type": "root", "children": {"loot": {"type": "literal", "children": {
+            "count": {"type": "argument", "parser": "brigadier:integer", "executable": true},
+            "mine": {"type": "literal", "executable": true},
+            "kill": {"type": "literal", "executable": true},
+            "loot": {"type": "literal", "executable": true},
+            "fish": {"type": "literal", "executable": true}
+        }}}}"#;
+        let cmds = util::commands::Commands::generate(serde_json::from_str(json).unwrap());
+        assert_eq!(cmds.help(), vec!["/loot (fish|kill|loot|mine|<count>)"]);
+        assert_eq!(
+            cmds.all_usage(cmds.root_index()),
+            vec![
+                "loot fish",
+                "loot kill",
+                "loot loot",
+                "loot mine",
+                "loot <count>"
+            ]
+        );
+    }
+
+    #[test]
+    fn redirect_cycle() {
+        let json = r#"{"type": "root", "children": {
+            "a": {"type": "literal", "redirect": ["b"]},
+            "b": {"type": "literal", "redirect": ["a"]}
+        }}"#;
+        let cmds = util::commands::Commands::generate(serde_json::from_str(json).unwrap());
+        let a = cmds.find_path(&["a"]).unwrap();
+        assert_eq!(cmds.next_nodes(a).count(), 0);
+        let (ast, trace) = CommandParser::new(&cmds).parse_traced("a b");
+        assert_eq!(ast.root().to_string(), "a b");
+        assert!(trace
+            .messages()
+            .any(|m| m == "redirects go around in a circle"));
+    }
+
+    #[test]
+    fn command_paths() {
+        let cmds = commands();
+        let names = |path: Vec<_>| {
+            path.into_iter()
+                .map(|i| cmds[i].name())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let score = cmds.find_path(&["execute", "if", "score"]).unwrap();
+        assert_eq!(cmds[score].name(), "score");
+        assert_eq!(names(cmds.path(score)), " execute if score");
+        let execute = cmds.find_path(&["execute"]).unwrap();
+        assert_eq!(cmds[cmds[score].parent().unwrap()].parent(), Some(execute));
+        assert_eq!(cmds.root().parent(), None);
+        // Redirects are followed, but the path stays in the tree
+        let at = cmds.find_path(&["execute", "as", "targets", "at"]).unwrap();
+        assert_eq!(cmds[at].parent(), Some(execute));
+        assert_eq!(cmds.find_path(&["execute", "as", "at"]), None);
+        assert_eq!(
+            cmds.find_path(&["tp", "destination"]),
+            cmds.find_path(&["teleport", "destination"])
+        );
+
+        let paths = cmds.executable_paths().map(names).collect::<Vec<_>>();
+        assert!(paths.contains(&" give targets item".to_owned()));
+        assert!(paths.contains(&" give targets item count".to_owned()));
+        assert!(!paths.contains(&" give targets".to_owned()));
+        assert_eq!(paths.len(), cmds.executable_paths().count());
+        assert!(paths.windows(2).all(|w| w[0] < w[1]), "{:?}", paths);
+
+        let item = cmds.find_path(&["give", "targets"]).unwrap();
+        assert_eq!(cmds.next_parser_types(item), vec![ParserType::ItemStack]);
+        let types = cmds.next_parser_types(cmds.find_path(&["teleport"]).unwrap());
+        assert_eq!(types.len(), 2);
+        assert!(types.contains(&ParserType::Vec3));
+        let run = cmds.find_path(&["execute", "run"]).unwrap();
+        assert_eq!(
+            cmds.next_nodes(run).count(),
+            cmds.root().children(&cmds).count()
+        );
+        assert!(cmds.next_parser_types(run).is_empty());
+    }
+}
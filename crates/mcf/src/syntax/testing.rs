@@ -1,9 +1,174 @@
-use super::{group::McGroupType, lexer, tokens::McTokenKind, McParser, McfLang};
-use mcfunction_parse::{parser::Parser, Ast, AstView, SyntaxKind, Token};
+use super::{cst, group::McGroupType, lexer, tokens::McTokenKind, McParser, McfLang};
+use mcfunction_data::{
+    reports::{BlockData, BlockState, RegistryEntry, RegistryInfo},
+    ReportDatabase,
+};
+use mcfunction_db::{DataType, FsDatabaseStorage, NamespaceId};
+use mcfunction_parse::{ast::CstNode, parser::Parser, Ast, AstView, SyntaxKind, Token};
+use relative_path::RelativePathBuf;
 
-use std::fmt::Write;
+use util::{
+    arena::Arena,
+    commands::{CommandNode, Commands},
+};
 
-pub fn format_astnode(node: AstView<&str, McfLang>, indlevel: usize) -> String {
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+    sync::Arc,
+};
+
+pub fn commands() -> Commands {
+    let root: CommandNode =
+        serde_json::from_str(include_str!("../../test_data/commands.json")).unwrap();
+    Commands::generate(root)
+}
+
+/// A small set of reports for testing, with the test commands.
+#[derive(Debug)]
+pub struct Reports {
+    commands: Commands,
+    registries: HashMap<String, RegistryInfo>,
+    blocks: HashMap<String, BlockData>,
+}
+
+impl Reports {
+    pub fn new() -> Self {
+        Reports {
+            commands: commands(),
+            registries: HashMap::new(),
+            blocks: HashMap::new(),
+        }
+    }
+
+    /// Adds a registry with the entries in `minecraft:`, unless they have a namespace.
+    pub fn registry(mut self, id: &str, entries: &[&str]) -> Self {
+        let entries = entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let id = if e.contains(':') {
+                    e.to_string()
+                } else {
+                    format!("minecraft:{}", e)
+                };
+                (id, RegistryEntry { protocol_id: i })
+            })
+            .collect();
+        self.registries.insert(
+            id.to_owned(),
+            RegistryInfo {
+                default: None,
+                protocol_id: self.registries.len(),
+                entries,
+            },
+        );
+        self
+    }
+
+    /// Adds a block with a state for every combination of the properties, numbered from `first_id`.
+    pub fn block(mut self, id: &str, first_id: usize, properties: &[(&str, &[&str])]) -> Self {
+        let mut states: Vec<HashMap<String, String>> = vec![HashMap::new()];
+        for (name, values) in properties {
+            states = states
+                .into_iter()
+                .flat_map(|state| {
+                    values.iter().map(move |v| {
+                        let mut state = state.clone();
+                        state.insert(name.to_string(), v.to_string());
+                        state
+                    })
+                })
+                .collect();
+        }
+        self.blocks.insert(
+            id.to_owned(),
+            BlockData {
+                properties: properties
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.iter().map(|v| v.to_string()).collect()))
+                    .collect(),
+                states: states
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, properties)| BlockState {
+                        id: first_id + i,
+                        properties,
+                        default: i == 0,
+                    })
+                    .collect(),
+            },
+        );
+        self
+    }
+}
+
+impl ReportDatabase for Reports {
+    fn block_data(&self, id: &str) -> Option<&BlockData> {
+        self.blocks.get(id)
+    }
+
+    fn registry_data(&self, id: &str) -> Option<&RegistryInfo> {
+        self.registries.get(id)
+    }
+
+    fn commands(&self) -> &Commands {
+        &self.commands
+    }
+}
+
+/// A file database for testing, holding only the names of item tags.
+#[salsa::database(FsDatabaseStorage)]
+#[derive(Default)]
+pub struct Files {
+    runtime: salsa::Runtime<Files>,
+}
+
+impl salsa::Database for Files {
+    fn salsa_runtime(&self) -> &salsa::Runtime<Self> {
+        &self.runtime
+    }
+
+    fn salsa_runtime_mut(&mut self) -> &mut salsa::Runtime<Self> {
+        &mut self.runtime
+    }
+}
+
+impl fmt::Debug for Files {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Files").finish()
+    }
+}
+
+impl Files {
+    /// Makes a database with the namespaces `names` and the item tags `tags`, which all need a namespace.
+    ///
+    /// Only the namespaces of `tags` have any data.
+    pub fn with_namespaces(names: &[&str], tags: &[&str]) -> Self {
+        let mut namespaces: HashMap<&str, HashSet<RelativePathBuf>> =
+            names.iter().map(|n| (*n, HashSet::new())).collect();
+        for tag in tags {
+            let (namespace, path) = tag.split_at(tag.find(':').expect("Tag has no namespace"));
+            namespaces
+                .entry(namespace)
+                .or_default()
+                .insert(RelativePathBuf::from(format!("{}.json", &path[1..])));
+        }
+        let mut files = Files::default();
+        let mut ids: Arena<NamespaceId, ()> = Arena::new();
+        let namespaces = namespaces.into_iter().map(|(namespace, tags)| {
+            let mut data = HashMap::new();
+            if !tags.is_empty() {
+                data.insert(DataType::ItemTags, tags);
+            }
+            (namespace.to_owned(), ids.push(()), data)
+        });
+        mcfunction_db::set_namespaces(&mut files, namespaces);
+        files
+    }
+}
+
+pub fn format_astnode<T: AsRef<str>>(node: AstView<T, McfLang>, indlevel: usize) -> String {
     let ind = "    ".repeat(indlevel);
     let mut out = String::new();
     match node.kind() {
@@ -112,3 +277,19 @@ pub fn parse<'a, F: FnMut(&mut McParser)>(i: &'a str, mut f: F) -> Ast<&'a str,
     }
     parser.build(false)
 }
+
+/// Parses a single line with `f` and casts the first node it made.
+#[allow(clippy::arc_with_non_send_sync)]
+pub fn parse_cst<C, F>(i: &str, f: F) -> C
+where
+    C: CstNode<String = Arc<str>, Language = McfLang, Node = cst::Node>,
+    F: FnMut(&mut McParser),
+{
+    let ast = Arc::new(parse(i, f).retype_src::<Arc<str>>());
+    let ind = ast
+        .root()
+        .first_child()
+        .expect("Nothing was parsed")
+        .index();
+    Ast::cst_index(ast, ind).unwrap_or_else(|_| panic!("The node has the wrong type"))
+}
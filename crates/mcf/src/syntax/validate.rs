@@ -0,0 +1,808 @@
+//! Checks of parsed commands that the grammar can't do by itself,
+//! such as the bounds of numbers given by the command tree.
+
+use super::{
+    cst::{self, SelectorKind, SelectorValue, NH},
+    grammar::selector::{selector_argument, SelectorArgumentInfo, SelectorRepeat},
+};
+use mcfunction_data::{reports::RegistryInfo, ReportDatabase};
+use mcfunction_db::{DataType, FsDatabase};
+use mcfunction_parse::{ast::CstNode, Diagnostic, Label, Span};
+use relative_path::RelativePathBuf;
+use std::fmt;
+use util::{
+    commands::{CommandNodeType, Commands, EntityAmount, EntityType, ParserType, Range},
+    suggest::did_you_mean,
+    Identifier,
+};
+
+pub struct Validator<'c> {
+    commands: &'c Commands,
+    reports: Option<&'c dyn ReportDatabase>,
+    files: Option<&'c dyn FsDatabase>,
+}
+
+impl<'c> Validator<'c> {
+    /// Constructs a validator for files that were parsed with `commands`.
+    pub fn new(commands: &'c Commands) -> Self {
+        Validator {
+            commands,
+            reports: None,
+            files: None,
+        }
+    }
+
+    /// Also checks ids against the registries in `reports`.
+    ///
+    /// Without reports, any resource location is accepted.
+    pub fn with_reports(self, reports: &'c dyn ReportDatabase) -> Self {
+        Validator {
+            reports: Some(reports),
+            ..self
+        }
+    }
+
+    /// Also checks tags against the ones defined by the datapacks in `files`.
+    ///
+    /// Without files, any tag is accepted. The namespaces of `files` have to be set,
+    /// see [`set_namespaces`].
+    ///
+    /// [`set_namespaces`]: ../../../mcfunction_db/fn.set_namespaces.html
+    pub fn with_files(self, files: &'c dyn FsDatabase) -> Self {
+        Validator {
+            files: Some(files),
+            ..self
+        }
+    }
+
+    /// Validates every command in the file, returning the problems in source order.
+    ///
+    /// Arguments with syntax errors are skipped, because the parser already reports those.
+    pub fn validate<N: NH>(&self, file: &cst::File<N>) -> Vec<Diagnostic> {
+        let mut out = vec![];
+        for command in file.lines().filter_map(|l| l.command()) {
+            for node in command.nodes() {
+                self.node(&node, &mut out);
+            }
+        }
+        out.sort_by_key(|d| d.span().start());
+        out
+    }
+
+    fn node<N: NH>(&self, node: &cst::CommandNode<N>, out: &mut Vec<Diagnostic>) {
+        let parser_type = match self.commands[node.index()].node_type() {
+            CommandNodeType::Argument { parser_type } => parser_type,
+            _ => return,
+        };
+        match parser_type {
+            ParserType::Integer { properties } => {
+                if let Some(int) = node.child_type::<cst::Integer<_>>(self.commands) {
+                    match int.string().parse::<i32>() {
+                        Ok(value) => {
+                            out.extend(check_bounds("Integer", &int, value, properties));
+                        }
+                        // The game can't read an integer that doesn't fit in an `i32` at all
+                        Err(_) => {
+                            let span = int.view().span();
+                            out.push(
+                                Diagnostic::error(
+                                    format!("Invalid integer '{}'", int.string()),
+                                    span,
+                                )
+                                .with_label(Label::primary(span, "")),
+                            );
+                        }
+                    }
+                }
+            }
+            ParserType::Float {
+                properties: Some(properties),
+            } => {
+                if let Some(float) = node.child_type::<cst::FloatToken<_>>(self.commands) {
+                    // The game reads floats as `f32`, so the bounds are compared without widening them
+                    if let Ok(value) = float.string().parse::<f32>() {
+                        out.extend(check_bounds("Float", &float, value, properties));
+                    }
+                }
+            }
+            ParserType::Entity { properties } => {
+                if let Some(selector) = node.child_type::<cst::Selector<_>>(self.commands) {
+                    let single = matches!(properties.amount, EntityAmount::Single);
+                    let players = matches!(properties.entity_type, EntityType::Players);
+                    entity(&selector, single, players, true, out);
+                    selector_arguments(&selector, out);
+                    player_name(&selector, out);
+                }
+            }
+            ParserType::GameProfile => {
+                if let Some(selector) = node.child_type::<cst::Selector<_>>(self.commands) {
+                    entity(&selector, false, true, false, out);
+                    selector_arguments(&selector, out);
+                    player_name(&selector, out);
+                }
+            }
+            ParserType::ScoreHolder { properties } => {
+                if let Some(selector) = node.child_type::<cst::Selector<_>>(self.commands) {
+                    let single = matches!(properties.amount, EntityAmount::Single);
+                    entity(&selector, single, false, true, out);
+                    selector_arguments(&selector, out);
+                }
+            }
+            ParserType::BlockState | ParserType::BlockPredicate => {
+                if let Some(state) = node.child_type::<cst::BlockState<_>>(self.commands) {
+                    self.block_state(&state, out);
+                }
+            }
+            ParserType::ItemStack => {
+                if let Some(item) = node.child_type::<cst::ItemStack<_>>(self.commands) {
+                    if let Some(loc) = item.id() {
+                        self.registry_entry(&loc, "item", "item", out);
+                    }
+                }
+            }
+            ParserType::ItemPredicate => {
+                if let Some(item) = node.child_type::<cst::ItemPredicate<_>>(self.commands) {
+                    match item.id() {
+                        Some(loc) if item.is_tag() => self.item_tag(&loc, out),
+                        Some(loc) => self.registry_entry(&loc, "item", "item", out),
+                        None => {}
+                    }
+                }
+            }
+            ParserType::EntitySummon => self.registry(node, "entity_type", "entity type", out),
+            ParserType::MobEffect => self.registry(node, "mob_effect", "effect", out),
+            ParserType::ItemEnchantment => self.registry(node, "enchantment", "enchantment", out),
+            ParserType::Particle => self.registry(node, "particle_type", "particle", out),
+            ParserType::Dimension => self.registry(node, "dimension_type", "dimension", out),
+            _ => {}
+        }
+    }
+
+    /// Checks that the block exists and that its properties are valid, giving the same errors as the game.
+    ///
+    /// Block tags are skipped, since they come from data packs rather than the reports.
+    fn block_state<N: NH>(&self, state: &cst::BlockState<N>, out: &mut Vec<Diagnostic>) {
+        let reports = match self.reports {
+            Some(v) => v,
+            None => return,
+        };
+        if state.is_tag() {
+            return;
+        }
+        let loc = match state.id() {
+            Some(v) => v,
+            None => return,
+        };
+        let id = match loc.id() {
+            Some(v) => v,
+            None => return,
+        };
+        let data = match reports.block_data(&id.to_string()) {
+            Some(v) => v,
+            None => {
+                let span = loc.view().span();
+                let mut diagnostic =
+                    Diagnostic::error(format!("Unknown block type '{}'", id), span)
+                        .with_label(Label::primary(span, ""));
+                let blocks = reports.registry_data("minecraft:block");
+                if let Some(note) = blocks.and_then(|b| suggest_entry(&id, b)) {
+                    diagnostic = diagnostic.with_note(note);
+                }
+                out.push(diagnostic);
+                return;
+            }
+        };
+        let mut seen: Vec<(&str, Span)> = vec![];
+        for property in state.properties() {
+            let key = match property.key() {
+                Some(v) => v,
+                None => continue,
+            };
+            let span = key.view().span();
+            let (name, values) = match data.properties.get_key_value(key.string()) {
+                Some(v) => v,
+                None => {
+                    let mut diagnostic = Diagnostic::error(
+                        format!("Block {} does not accept '{}' property", id, key.string()),
+                        span,
+                    )
+                    .with_label(Label::primary(span, "unknown property"));
+                    let names = data.properties.keys().map(String::as_str);
+                    if let Some(suggestion) = did_you_mean(key.string(), names) {
+                        diagnostic =
+                            diagnostic.with_note(format!("did you mean '{}'?", suggestion));
+                    }
+                    out.push(diagnostic);
+                    continue;
+                }
+            };
+            if let Some((_, first)) = seen.iter().find(|(k, _)| k == name) {
+                out.push(
+                    Diagnostic::error(
+                        format!(
+                            "Property '{}' can only be set once for block {}",
+                            key.string(),
+                            id
+                        ),
+                        span,
+                    )
+                    .with_label(Label::primary(span, "repeated here"))
+                    .with_label(Label::secondary(*first, "already given here")),
+                );
+            } else {
+                seen.push((name, span));
+            }
+            if let Some(value) = property.value() {
+                if !values.iter().any(|v| v == value.string()) {
+                    let value_span = value.view().span();
+                    out.push(
+                        Diagnostic::error(
+                            format!(
+                                "Block {} does not accept '{}' for {} property",
+                                id,
+                                value.string(),
+                                key.string()
+                            ),
+                            value_span,
+                        )
+                        .with_label(Label::primary(value_span, ""))
+                        .with_note(format!("the valid values are {}", values.join(", "))),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks that the resource location of a node is in the registry `minecraft:<registry>`.
+    fn registry<N: NH>(
+        &self,
+        node: &cst::CommandNode<N>,
+        registry: &str,
+        name: &str,
+        out: &mut Vec<Diagnostic>,
+    ) {
+        if let Some(loc) = node.child_type::<cst::ResourceLocation<_>>(self.commands) {
+            self.registry_entry(&loc, registry, name, out);
+        }
+    }
+
+    fn registry_entry<N: NH>(
+        &self,
+        loc: &cst::ResourceLocation<N>,
+        registry: &str,
+        name: &str,
+        out: &mut Vec<Diagnostic>,
+    ) {
+        let info = match self
+            .reports
+            .and_then(|r| r.registry_data(&format!("minecraft:{}", registry)))
+        {
+            Some(v) => v,
+            None => return,
+        };
+        let id = match loc.id() {
+            Some(v) => v,
+            None => return,
+        };
+        if info.entries.contains_key(&id.to_string()) {
+            return;
+        }
+        let span = loc.view().span();
+        let mut diagnostic =
+            Diagnostic::error(format!("Unknown {} '{}'", name, id), span).with_label(
+                Label::primary(span, format!("searched in namespace '{}'", id.namespace())),
+            );
+        if let Some(note) = suggest_entry(&id, info) {
+            diagnostic = diagnostic.with_note(note);
+        }
+        out.push(diagnostic);
+    }
+
+    /// Checks that an item tag is defined by one of the datapacks.
+    fn item_tag<N: NH>(&self, loc: &cst::ResourceLocation<N>, out: &mut Vec<Diagnostic>) {
+        let files = match self.files {
+            Some(v) => v,
+            None => return,
+        };
+        let id = match loc.id() {
+            Some(v) => v,
+            None => return,
+        };
+        let tags = if files.namespaces().contains(id.namespace()) {
+            let namespace = files.namespace_id(id.namespace().to_owned());
+            Some(files.namespace_info(namespace, DataType::ItemTags))
+        } else {
+            None
+        };
+        let file = RelativePathBuf::from(format!("{}.json", id.path()));
+        if tags.as_ref().is_some_and(|t| t.contains(&file)) {
+            return;
+        }
+        let span = loc.view().span();
+        let mut diagnostic =
+            Diagnostic::error(format!("Unknown item tag '#{}'", id), span).with_label(
+                Label::primary(span, format!("searched in namespace '{}'", id.namespace())),
+            );
+        match &tags {
+            Some(tags) => {
+                let paths = tags.iter().filter_map(|t| t.as_str().strip_suffix(".json"));
+                if let Some(suggestion) = did_you_mean(id.path(), paths) {
+                    diagnostic = diagnostic.with_note(format!(
+                        "did you mean '#{}:{}'?",
+                        id.namespace(),
+                        suggestion
+                    ));
+                }
+            }
+            None => {
+                diagnostic = diagnostic.with_note(format!(
+                    "no datapack has a namespace named '{}'",
+                    id.namespace()
+                ));
+            }
+        }
+        out.push(diagnostic);
+    }
+}
+
+/// Suggests an entry of `registry` in the same namespace as the unknown `id`, as a note.
+fn suggest_entry(id: &Identifier, registry: &RegistryInfo) -> Option<String> {
+    // Only the paths are compared, so that a long namespace doesn't allow more edits
+    let prefix = format!("{}:", id.namespace());
+    let paths = registry
+        .entries
+        .keys()
+        .filter_map(|e| e.strip_prefix(&prefix));
+    did_you_mean(id.path(), paths).map(|s| format!("did you mean '{}{}'?", prefix, s))
+}
+
+/// Checks the amount and types of entities that a selector can select,
+/// giving the same errors as the game.
+///
+/// Entity arguments let `@s` through when only players are allowed, since the game checks
+/// its type when the command runs, but game profiles reject it right away.
+fn entity<N: NH>(
+    selector: &cst::Selector<N>,
+    single: bool,
+    players: bool,
+    allow_self: bool,
+    out: &mut Vec<Diagnostic>,
+) {
+    let kind = match selector.kind() {
+        Some(v) => v,
+        None => return,
+    };
+    let mut limit = match kind {
+        SelectorKind::AllPlayers | SelectorKind::AllEntities => None,
+        _ => Some(1),
+    };
+    let mut includes_entities = matches!(
+        kind,
+        SelectorKind::ExecutingEntity | SelectorKind::AllEntities
+    );
+    for arg in selector.arguments() {
+        let key = match arg.key() {
+            Some(v) => v,
+            None => continue,
+        };
+        let value = match arg.value() {
+            Some(v) => v,
+            None => continue,
+        };
+        match (key.string(), value) {
+            ("limit", SelectorValue::Integer(_)) => {
+                limit = arg.value().and_then(|v| v.integer()?.value());
+            }
+            ("type", SelectorValue::ResourceLocation(_)) if !arg.inverted() && !arg.is_tag() => {
+                if let Some(id) = arg.value().and_then(|v| v.resource_location()) {
+                    if id.namespace() == "minecraft" && id.path() == "player" {
+                        includes_entities = false;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let span = selector.view().span();
+    if single && limit.is_none_or(|l| l > 1) {
+        let message = if players {
+            "Only one player is allowed, but the provided selector allows more than one"
+        } else {
+            "Only one entity is allowed, but the provided selector allows more than one"
+        };
+        out.push(Diagnostic::error(message, span).with_label(Label::primary(span, "")));
+    }
+    if players && includes_entities && !(allow_self && kind == SelectorKind::ExecutingEntity) {
+        out.push(
+            Diagnostic::error(
+                "Only players may be affected by this command, but the provided selector includes entities",
+                span,
+            )
+            .with_label(Label::primary(span, "")),
+        );
+    }
+}
+
+/// Checks that the selector arguments exist, and that they are only inverted
+/// or repeated where the game allows it.
+fn selector_arguments<N: NH>(selector: &cst::Selector<N>, out: &mut Vec<Diagnostic>) {
+    let is_self = selector.kind() == Some(SelectorKind::ExecutingEntity);
+    let mut seen: Vec<(&SelectorArgumentInfo, bool, Span)> = vec![];
+    for arg in selector.arguments() {
+        let key = match arg.key() {
+            Some(v) => v,
+            None => continue,
+        };
+        let span = key.view().span();
+        let info = match selector_argument(key.string()) {
+            Some(v) => v,
+            None => {
+                out.push(
+                    Diagnostic::error(format!("Unknown option '{}'", key.string()), span)
+                        .with_label(Label::primary(span, "unknown selector argument")),
+                );
+                continue;
+            }
+        };
+        let inverted = arg.inverted();
+        if inverted && !info.invertible {
+            out.push(
+                Diagnostic::error(format!("Option '{}' can't be inverted", info.name), span)
+                    .with_label(Label::primary(span, "")),
+            );
+        }
+        if !info.allow_self && is_self {
+            out.push(
+                Diagnostic::error(
+                    format!("Option '{}' isn't applicable here", info.name),
+                    span,
+                )
+                .with_label(Label::primary(span, "not applicable to `@s`")),
+            );
+        }
+        let mut previous = seen.iter().rev().filter(|(i, _, _)| i.name == info.name);
+        let repeated = match info.repeat {
+            SelectorRepeat::Once => previous.next(),
+            SelectorRepeat::Many => None,
+            SelectorRepeat::InvertedOnly => previous.find(|(_, inv, _)| !inv),
+        };
+        if let Some((_, _, first)) = repeated {
+            let mut diag = Diagnostic::error(
+                format!("Option '{}' isn't applicable here", info.name),
+                span,
+            )
+            .with_label(Label::primary(span, "repeated here"))
+            .with_label(Label::secondary(*first, "already given here"));
+            if info.repeat == SelectorRepeat::InvertedOnly {
+                diag = diag.with_note(format!(
+                    "'{}' can only be repeated if every value is inverted",
+                    info.name
+                ));
+            }
+            out.push(diag);
+        }
+        seen.push((info, inverted, span));
+    }
+}
+
+/// Warns about names that can't belong to a player, such as fake players on the scoreboard.
+///
+/// The game only finds entities by name if they are players,
+/// so a name like that can never select anything.
+fn player_name<N: NH>(selector: &cst::Selector<N>, out: &mut Vec<Diagnostic>) {
+    let name = match selector.name() {
+        Some(v) => v,
+        None => return,
+    };
+    let text = name.string();
+    let valid = (3..=16).contains(&text.len())
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        let span = name.view().span();
+        out.push(
+            Diagnostic::warning(format!("'{}' is not a valid player name", text), span)
+                .with_label(Label::primary(span, ""))
+                .with_note("player names are 3 to 16 letters, digits and underscores"),
+        );
+    }
+}
+
+/// Reports the bound that `value` breaks, like the game does, along with the whole range.
+fn check_bounds<C: CstNode, T: Copy + PartialOrd + fmt::Display>(
+    name: &str,
+    node: &C,
+    value: T,
+    bounds: Range<T>,
+) -> Option<Diagnostic> {
+    if bounds.contains(value) {
+        return None;
+    }
+    let view = node.view();
+    let message = match (bounds.min(), bounds.max()) {
+        (Some(min), _) if value < min => format!(
+            "{} must not be less than {}, found {}",
+            name,
+            min,
+            view.string()
+        ),
+        (_, Some(max)) => format!(
+            "{} must not be more than {}, found {}",
+            name,
+            max,
+            view.string()
+        ),
+        _ => return None,
+    };
+    let range = match (bounds.min(), bounds.max()) {
+        (Some(min), Some(max)) => format!("allowed range is {} to {}", min, max),
+        (Some(min), None) => format!("allowed range is {} or more", min),
+        (None, Some(max)) => format!("allowed range is {} or less", max),
+        (None, None) => unreachable!(),
+    };
+    Some(Diagnostic::error(message, view.span()).with_label(Label::primary(view.span(), range)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Validator;
+    use crate::syntax::{
+        cst,
+        testing::{commands, Files, Reports},
+        CommandParser,
+    };
+    use mcfunction_parse::{Ast, Diagnostic};
+    use std::sync::Arc;
+
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn diagnostics(src: &str, reports: Option<&Reports>, files: Option<&Files>) -> Vec<Diagnostic> {
+        let cmds = commands();
+        let ast = CommandParser::new(&cmds).parse(src);
+        assert_eq!(ast.diagnostics(), vec![], "{} has syntax errors", src);
+        let file: cst::File<cst::Node> =
+            Ast::cst_root(Arc::new(ast.retype_src::<Arc<str>>())).unwrap();
+        let mut validator = Validator::new(&cmds);
+        if let Some(reports) = reports {
+            validator = validator.with_reports(reports);
+        }
+        if let Some(files) = files {
+            validator = validator.with_files(files);
+        }
+        validator.validate(&file)
+    }
+
+    fn validate(src: &str) -> Vec<String> {
+        diagnostics(src, None, None)
+            .iter()
+            .map(|d| format!("{} at {}", d.message(), d.span()))
+            .collect()
+    }
+
+    // Like `validate`, but with the data to check ids against, and with the label and notes
+    fn check(src: &str, reports: &Reports, files: Option<&Files>) -> Vec<String> {
+        diagnostics(src, Some(reports), files)
+            .iter()
+            .map(|d| {
+                let label = match d.labels().first().map(|l| l.message()) {
+                    Some(label) if !label.is_empty() => format!(" ({})", label),
+                    _ => String::new(),
+                };
+                format!("{} at {}{} {:?}", d.message(), d.span(), label, d.notes())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn integer_bounds() {
+        assert_eq!(
+            validate("effect give @s speed 1000000 256\ngive @s stone 0\ngive @s stone 1"),
+            vec![
+                "Integer must not be more than 255, found 256 at 0:29 - 0:32",
+                "Integer must not be less than 1, found 0 at 1:14 - 1:15",
+            ]
+        );
+        assert_eq!(
+            validate("give @s stone 3000000000"),
+            vec!["Invalid integer '3000000000' at 0:14 - 0:24"]
+        );
+        assert_eq!(
+            validate("effect give @s speed 1000000 255"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn float_bounds() {
+        // 0.1 can't be stored exactly, but the literal has to match the bound
+        assert_eq!(
+            validate(
+                "playsound 0.1
+playsound 2
+playsound 1.5"
+            ),
+            Vec::<String>::new()
+        );
+        let bounds = diagnostics(
+            "playsound 0.09
+playsound 2.01",
+            None,
+            None,
+        )
+        .iter()
+        .map(|d| {
+            format!(
+                "{} at {} ({})",
+                d.message(),
+                d.span(),
+                d.labels()[0].message()
+            )
+        })
+        .collect::<Vec<_>>();
+        assert_eq!(
+            bounds,
+            vec![
+                "Float must not be less than 0.1, found 0.09 at 0:10 - 0:14 (allowed range is 0.1 to 2)",
+                "Float must not be more than 2, found 2.01 at 1:10 - 1:14 (allowed range is 0.1 to 2)",
+            ]
+        );
+    }
+
+    #[test]
+    fn entity_amount() {
+        assert_eq!(
+            validate("teleport @e\nteleport @a[limit=2]\nteleport @e[limit=1]\nteleport @r"),
+            vec![
+                "Only one entity is allowed, but the provided selector allows more than one at 0:9 - 0:11",
+                "Only one entity is allowed, but the provided selector allows more than one at 1:9 - 1:20",
+            ]
+        );
+        // The game only rejects `*` for a single score holder when the command runs
+        assert!(validate("execute if score * a = @s a run say hi").is_empty());
+    }
+
+    #[test]
+    fn entity_players() {
+        assert_eq!(
+            validate(
+                "give @e[type=cow] stone\n\
+                 give @e[type=!player] stone\n\
+                 give @e[type=minecraft:player] stone\n\
+                 give @s stone\n\
+                 give @a stone"
+            ),
+            vec![
+                "Only players may be affected by this command, but the provided selector includes entities at 0:5 - 0:17",
+                "Only players may be affected by this command, but the provided selector includes entities at 1:5 - 1:21",
+            ]
+        );
+        // Game profiles don't make an exception for `@s`
+        assert_eq!(
+            validate("op @s
+op @a
+op @e[type=player]"),
+            vec![
+                "Only players may be affected by this command, but the provided selector includes entities at 0:3 - 0:5",
+            ]
+        );
+    }
+
+    #[test]
+    fn selector_arguments() {
+        assert_eq!(
+            validate(
+                "execute as @e[foo=1,tag=a,tag=!b,limit=1,limit=2] run say hi\n\
+                 execute as @e[type=!cow,type=!pig,team=a,team=!b] run say hi\n\
+                 execute as @s[limit=!1,sort=nearest,gamemode=!creative] run say hi"
+            ),
+            vec![
+                "Unknown option 'foo' at 0:14 - 0:17",
+                "Option 'limit' isn't applicable here at 0:41 - 0:46",
+                "Option 'team' isn't applicable here at 1:41 - 1:45",
+                "Option 'limit' can't be inverted at 2:14 - 2:19",
+                "Option 'limit' isn't applicable here at 2:14 - 2:19",
+                "Option 'sort' isn't applicable here at 2:23 - 2:27",
+            ]
+        );
+    }
+
+    #[test]
+    fn player_names() {
+        assert_eq!(
+            validate("give Steve stone\ngive a.b stone\ngive ThisNameIsTooLong stone\nexecute if score a.b a = @s a run say hi"),
+            vec![
+                "'a.b' is not a valid player name at 1:5 - 1:8",
+                "'ThisNameIsTooLong' is not a valid player name at 2:5 - 2:22",
+            ]
+        );
+    }
+
+    #[test]
+    fn registries() {
+        let reports = Reports::new().registry(
+            "minecraft:mob_effect",
+            &["speed", "slowness", "haste", "jump_boost"],
+        );
+        assert_eq!(
+            check(
+                "effect give @s speed\neffect give @s minecraft:haste\neffect give @s jump_boost",
+                &reports,
+                None
+            ),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check("effect give @s sped\neffect give @s slownes\neffect give @s foo:speed\neffect give @s nausea", &reports, None),
+            vec![
+                r#"Unknown effect 'minecraft:sped' at 0:15 - 0:19 (searched in namespace 'minecraft') ["did you mean 'minecraft:speed'?"]"#,
+                r#"Unknown effect 'minecraft:slownes' at 1:15 - 1:22 (searched in namespace 'minecraft') ["did you mean 'minecraft:slowness'?"]"#,
+                r#"Unknown effect 'foo:speed' at 2:15 - 2:24 (searched in namespace 'foo') []"#,
+                r#"Unknown effect 'minecraft:nausea' at 3:15 - 3:21 (searched in namespace 'minecraft') []"#,
+            ]
+        );
+        // Without reports, nothing is known about the registries
+        assert_eq!(validate("effect give @s sped"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn block_states() {
+        let reports = Reports::new()
+            .registry("minecraft:block", &["stone", "oak_stairs", "lever"])
+            .block("minecraft:stone", 1, &[])
+            .block(
+                "minecraft:oak_stairs",
+                2,
+                &[
+                    ("facing", &["north", "south"]),
+                    ("half", &["top", "bottom"]),
+                ],
+            );
+        assert_eq!(
+            check("setblock ~ ~ ~ stone\nsetblock ~ ~ ~ oak_stairs[half=top]\nexecute if block ~ ~ ~ #stairs[foo=bar] run say hi", &reports, None),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check("setblock ~ ~ ~ stnoe\nsetblock ~ ~ ~ foo:stone\nsetblock ~ ~ ~ oak_stairs[facin=north]", &reports, None),
+            vec![
+                r#"Unknown block type 'minecraft:stnoe' at 0:15 - 0:20 ["did you mean 'minecraft:stone'?"]"#,
+                r#"Unknown block type 'foo:stone' at 1:15 - 1:24 []"#,
+                r#"Block minecraft:oak_stairs does not accept 'facin' property at 2:26 - 2:31 (unknown property) ["did you mean 'facing'?"]"#,
+            ]
+        );
+        assert_eq!(
+            check(
+                "execute if block ~ ~ ~ oak_stairs[half=left,half=top] run say hi",
+                &reports,
+                None
+            ),
+            vec![
+                r#"Block minecraft:oak_stairs does not accept 'left' for half property at 0:39 - 0:43 ["the valid values are top, bottom"]"#,
+                r#"Property 'half' can only be set once for block minecraft:oak_stairs at 0:44 - 0:48 (repeated here) []"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn items() {
+        let reports = Reports::new().registry("minecraft:item", &["stick", "stone", "oak_planks"]);
+        let files = Files::with_namespaces(
+            &["baz"],
+            &["minecraft:planks", "minecraft:logs", "foo:wands"],
+        );
+        assert_eq!(
+            check("give @s stick\nclear @s minecraft:oak_planks{a:1b}\nclear @s #planks\nclear @s #foo:wands", &reports, Some(&files)),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check("give @s stik\nclear @s foo:stick 1\nclear @s #plank\nclear @s #foo:logs\nclear @s #bar:logs\nclear @s #baz:logs", &reports, Some(&files)),
+            vec![
+                r#"Unknown item 'minecraft:stik' at 0:8 - 0:12 (searched in namespace 'minecraft') ["did you mean 'minecraft:stick'?"]"#,
+                r#"Unknown item 'foo:stick' at 1:9 - 1:18 (searched in namespace 'foo') []"#,
+                r#"Unknown item tag '#minecraft:plank' at 2:10 - 2:15 (searched in namespace 'minecraft') ["did you mean '#minecraft:planks'?"]"#,
+                r#"Unknown item tag '#foo:logs' at 3:10 - 3:18 (searched in namespace 'foo') []"#,
+                r#"Unknown item tag '#bar:logs' at 4:10 - 4:18 (searched in namespace 'bar') ["no datapack has a namespace named 'bar'"]"#,
+                r#"Unknown item tag '#baz:logs' at 5:10 - 5:18 (searched in namespace 'baz') []"#,
+            ]
+        );
+        // Tags can't be checked without the datapacks
+        assert_eq!(diagnostics("clear @s #plank", Some(&reports), None), vec![]);
+    }
+}
@@ -0,0 +1,635 @@
+//! Owned values of command arguments, converted from the typed CST.
+//!
+//! Every conversion returns `None` if the node has a syntax error that leaves its value unknown.
+
+use super::cst::{self, CoordValue, SelectorKind, NH};
+use mcfunction_data::ReportDatabase;
+use mcfunction_parse::ast::CstNode;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+use util::{commands::Range, Identifier};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Uuid(pub u128);
+
+impl Uuid {
+    pub fn from_cst<N: NH>(node: &cst::Uuid<N>) -> Option<Self> {
+        node.value().map(Uuid)
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            self.0 >> 96,
+            self.0 >> 80 & 0xffff,
+            self.0 >> 64 & 0xffff,
+            self.0 >> 48 & 0xffff,
+            self.0 & 0xffff_ffff_ffff
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Time {
+    pub ticks: i64,
+}
+
+impl Time {
+    pub fn from_cst<N: NH>(node: &cst::Time<N>) -> Option<Self> {
+        Some(Time {
+            ticks: node.ticks()?,
+        })
+    }
+}
+
+/// A range of integers, such as `1..5`. A bound that is left out is `None`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IntRange {
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl IntRange {
+    pub fn from_cst<N: NH>(node: &cst::IntRangeArgument<N>) -> Option<Self> {
+        let bound = |v: Option<cst::FloatToken<_>>| match v {
+            Some(v) => v.string().parse().ok().map(Some),
+            None => Some(None),
+        };
+        Some(IntRange {
+            min: bound(node.min())?,
+            max: bound(node.max())?,
+        })
+    }
+
+    pub fn contains(&self, value: i32) -> bool {
+        Range::new(self.min, self.max).contains(value)
+    }
+}
+
+/// A range of numbers, such as `..2.5`. A bound that is left out is `None`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FloatRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl FloatRange {
+    pub fn from_cst<N: NH>(node: &cst::IntRangeArgument<N>) -> Option<Self> {
+        let bound = |v: Option<cst::FloatToken<_>>| match v {
+            Some(v) => v.value().map(Some),
+            None => Some(None),
+        };
+        Some(FloatRange {
+            min: bound(node.min())?,
+            max: bound(node.max())?,
+        })
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        Range::new(self.min, self.max).contains(value)
+    }
+}
+
+/// The three coordinates of a position, such as `~ ~1 ~`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Coordinates {
+    pub x: CoordValue,
+    pub y: CoordValue,
+    pub z: CoordValue,
+}
+
+impl Coordinates {
+    pub fn from_cst<N: NH>(node: &cst::Coord<N>) -> Option<Self> {
+        match coord_values(node)?.as_slice() {
+            &[x, y, z] => Some(Coordinates { x, y, z }),
+            _ => None,
+        }
+    }
+}
+
+/// The two coordinates of a column, a 2D vector or a rotation.
+///
+/// For a rotation, `x` is the yaw and `y` is the pitch.
+/// For a column, `y` is the z coordinate.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Coordinates2 {
+    pub x: CoordValue,
+    pub y: CoordValue,
+}
+
+impl Coordinates2 {
+    pub fn from_cst<N: NH>(node: &cst::Coord<N>) -> Option<Self> {
+        match coord_values(node)?.as_slice() {
+            &[x, y] => Some(Coordinates2 { x, y }),
+            _ => None,
+        }
+    }
+}
+
+fn coord_values<N: NH>(node: &cst::Coord<N>) -> Option<Vec<CoordValue>> {
+    node.parts().map(|p| p.value()).collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NbtValue {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    ByteArray(Vec<i8>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+    List(Vec<NbtValue>),
+    Compound(BTreeMap<String, NbtValue>),
+}
+
+impl NbtValue {
+    /// Converts an NBT value. Booleans become bytes, like they do in the game.
+    pub fn from_cst<N: NH>(node: &cst::NbtValue<N>) -> Option<Self> {
+        match node.as_ref() {
+            cst::NbtValue::Compound(_) => Self::from_compound(&node.as_ref().compound()?),
+            cst::NbtValue::Sequence(_) => Self::from_sequence(&node.as_ref().sequence()?),
+            cst::NbtValue::Number(_) => Self::from_number(&node.as_ref().number()?),
+            cst::NbtValue::String(v) => Some(NbtValue::String(string_value(v.view().string())?)),
+            cst::NbtValue::Boolean(_) => {
+                Some(NbtValue::Byte(node.as_ref().boolean()?.value()? as i8))
+            }
+        }
+    }
+
+    pub fn from_compound<N: NH>(node: &cst::NbtCompound<N>) -> Option<Self> {
+        let mut map = BTreeMap::new();
+        for entry in node.entries() {
+            let key = string_value(entry.key()?.view().string())?;
+            map.insert(key, Self::from_cst(&entry.value()?)?);
+        }
+        Some(NbtValue::Compound(map))
+    }
+
+    fn from_sequence<N: NH>(node: &cst::NbtSequence<N>) -> Option<Self> {
+        let entries = node
+            .entries()
+            .map(|v| Self::from_cst(&v))
+            .collect::<Option<Vec<_>>>()?;
+        Some(match node.seq_type() {
+            cst::NbtSequenceType::List => {
+                // Lists can only hold one type of tag
+                let first = entries.first().map(std::mem::discriminant);
+                if entries
+                    .iter()
+                    .any(|v| Some(std::mem::discriminant(v)) != first)
+                {
+                    return None;
+                }
+                NbtValue::List(entries)
+            }
+            cst::NbtSequenceType::ByteArray => NbtValue::ByteArray(
+                entries
+                    .into_iter()
+                    .map(|v| match v {
+                        NbtValue::Byte(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            cst::NbtSequenceType::IntArray => NbtValue::IntArray(
+                entries
+                    .into_iter()
+                    .map(|v| match v {
+                        NbtValue::Int(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            cst::NbtSequenceType::LongArray => NbtValue::LongArray(
+                entries
+                    .into_iter()
+                    .map(|v| match v {
+                        NbtValue::Long(v) => Some(v),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+            cst::NbtSequenceType::ErrorArray => return None,
+        })
+    }
+
+    fn from_number<N: NH>(node: &cst::NbtNumber<N>) -> Option<Self> {
+        if let Some(v) = node.byte() {
+            Some(NbtValue::Byte(v.string().parse().ok()?))
+        } else if let Some(v) = node.short() {
+            Some(NbtValue::Short(v.string().parse().ok()?))
+        } else if let Some(v) = node.long() {
+            Some(NbtValue::Long(v.string().parse().ok()?))
+        } else if let Some(v) = node.float() {
+            Some(NbtValue::Float(v.string().parse().ok()?))
+        } else if let Some(v) = node.double() {
+            Some(NbtValue::Double(v.value()?))
+        } else {
+            let v = node.untagged()?;
+            let text = v.string();
+            if text.contains(['.', 'e', 'E']) {
+                Some(NbtValue::Double(v.value()?))
+            } else {
+                // An integer that doesn't fit is read as a string by the game
+                Some(
+                    text.parse()
+                        .map(NbtValue::Int)
+                        .unwrap_or_else(|_| NbtValue::String(text.to_owned())),
+                )
+            }
+        }
+    }
+}
+
+/// A block state or a block predicate, such as `oak_stairs[facing=east]{a:1b}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockState {
+    pub id: Identifier,
+    /// Whether `id` is a block tag, which predicates can match against.
+    pub tag: bool,
+    /// The properties in the order they were written.
+    pub properties: Vec<(String, String)>,
+    pub nbt: Option<NbtValue>,
+}
+
+impl BlockState {
+    pub fn from_cst<N: NH>(node: &cst::BlockState<N>) -> Option<Self> {
+        let properties = node
+            .properties()
+            .map(|p| Some((p.key()?.string().to_owned(), p.value()?.string().to_owned())))
+            .collect::<Option<_>>()?;
+        Some(BlockState {
+            id: node.id()?.id()?,
+            tag: node.is_tag(),
+            properties,
+            nbt: match node.nbt() {
+                Some(nbt) => Some(NbtValue::from_compound(&nbt)?),
+                None => None,
+            },
+        })
+    }
+
+    /// Finds the id of this exact state in the block reports.
+    ///
+    /// This is `None` for tags, unknown blocks, and states that leave out a property,
+    /// since those can match more than one state.
+    pub fn state_id(&self, reports: &dyn ReportDatabase) -> Option<usize> {
+        if self.tag {
+            return None;
+        }
+        let data = reports.block_data(&self.id.to_string())?;
+        let properties: HashMap<&str, &str> = self
+            .properties
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        if properties.len() != self.properties.len() || properties.len() != data.properties.len() {
+            return None;
+        }
+        data.states
+            .iter()
+            .find(|state| {
+                state.properties.len() == properties.len()
+                    && state
+                        .properties
+                        .iter()
+                        .all(|(k, v)| properties.get(k.as_str()) == Some(&v.as_str()))
+            })
+            .map(|state| state.id)
+    }
+}
+
+/// An entity selector, such as `@e[type=!pig,limit=1]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntitySelector {
+    pub kind: SelectorKind,
+    /// The selector arguments in the order they were written.
+    pub filters: Vec<SelectorFilter>,
+}
+
+impl EntitySelector {
+    pub fn from_cst<N: NH>(node: &cst::Selector<N>) -> Option<Self> {
+        Some(EntitySelector {
+            kind: node.kind()?,
+            filters: node
+                .arguments()
+                .map(|a| {
+                    Some(SelectorFilter {
+                        key: a.key()?.string().to_owned(),
+                        inverted: a.inverted(),
+                        value: SelectorFilterValue::from_cst(&a.value()?)?,
+                    })
+                })
+                .collect::<Option<_>>()?,
+        })
+    }
+}
+
+/// A single `key=value` argument of a selector, or of a map inside a selector argument.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectorFilter {
+    pub key: String,
+    /// Whether the value is inverted with `!`.
+    pub inverted: bool,
+    pub value: SelectorFilterValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectorFilterValue {
+    /// A quoted or unquoted string, which can also be a resource location such as a type
+    /// or a keyword such as a game mode. Tags keep their `#`.
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    Range(FloatRange),
+    Nbt(NbtValue),
+    /// A map, such as the objectives of `scores={a=1..}`.
+    Map(Vec<SelectorFilter>),
+}
+
+impl SelectorFilterValue {
+    pub fn from_cst<N: NH>(node: &cst::SelectorValue<N>) -> Option<Self> {
+        Some(match node.as_ref() {
+            cst::SelectorValue::String(v) => {
+                SelectorFilterValue::String(string_value(v.view().string())?)
+            }
+            cst::SelectorValue::Unquoted(v)
+            | cst::SelectorValue::Sort(v)
+            | cst::SelectorValue::Gamemode(v) => {
+                SelectorFilterValue::String(v.view().string().to_owned())
+            }
+            cst::SelectorValue::ResourceLocation(v) => {
+                // A tag keeps the `#` in front of it
+                let text = v.view().string();
+                match v.view().prev_sibling() {
+                    Some(prev) if prev.string() == "#" => {
+                        SelectorFilterValue::String(format!("#{}", text))
+                    }
+                    _ => SelectorFilterValue::String(text.to_owned()),
+                }
+            }
+            cst::SelectorValue::Float(_) => {
+                SelectorFilterValue::Number(node.as_ref().float()?.value()?)
+            }
+            cst::SelectorValue::Integer(_) => {
+                SelectorFilterValue::Number(node.as_ref().integer()?.value()?.into())
+            }
+            cst::SelectorValue::True(_) => SelectorFilterValue::Boolean(true),
+            cst::SelectorValue::False(_) => SelectorFilterValue::Boolean(false),
+            cst::SelectorValue::Nbt(_) => {
+                SelectorFilterValue::Nbt(NbtValue::from_compound(&node.as_ref().nbt()?)?)
+            }
+            cst::SelectorValue::Range(_) => {
+                SelectorFilterValue::Range(FloatRange::from_cst(&node.as_ref().range()?)?)
+            }
+            cst::SelectorValue::Map(_) => SelectorFilterValue::Map(
+                node.as_ref()
+                    .map()?
+                    .entries()
+                    .map(|e| {
+                        Some(SelectorFilter {
+                            key: e.key()?.to_owned(),
+                            inverted: e.inverted(),
+                            value: Self::from_cst(&e.value()?)?,
+                        })
+                    })
+                    .collect::<Option<_>>()?,
+            ),
+        })
+    }
+}
+
+/// The target of an entity argument, which is either a selector, a player name or a UUID.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntityTarget {
+    Selector(EntitySelector),
+    Name(String),
+    Uuid(Uuid),
+}
+
+impl EntityTarget {
+    pub fn from_cst<N: NH>(node: &cst::Selector<N>) -> Option<Self> {
+        if let Some(uuid) = node.uuid() {
+            Some(EntityTarget::Uuid(Uuid::from_cst(&uuid)?))
+        } else if let Some(name) = node.name() {
+            Some(EntityTarget::Name(name.string().to_owned()))
+        } else {
+            Some(EntityTarget::Selector(EntitySelector::from_cst(node)?))
+        }
+    }
+}
+
+/// Gets the value of a string that might be quoted, undoing the escapes in it.
+///
+/// Like in the game, only the quote and `\` can be escaped.
+pub fn string_value(text: &str) -> Option<String> {
+    let quote = match text.chars().next() {
+        Some(c @ '"') | Some(c @ '\'') => c,
+        _ => return Some(text.to_owned()),
+    };
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text[1..].chars();
+    loop {
+        match chars.next()? {
+            '\\' => match chars.next()? {
+                c if c == quote || c == '\\' => out.push(c),
+                _ => return None,
+            },
+            c if c == quote => break,
+            c => out.push(c),
+        }
+    }
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::{
+        grammar,
+        testing::{parse_cst, Reports},
+    };
+
+    #[test]
+    fn coordinates() {
+        let pos = Coordinates::from_cst(&parse_cst("~ ^-1.5 3", grammar::coord::coord));
+        assert_eq!(
+            pos,
+            Some(Coordinates {
+                x: CoordValue::Relative(0.0),
+                y: CoordValue::Local(-1.5),
+                z: CoordValue::Absolute(3.0),
+            })
+        );
+        let rot = Coordinates::from_cst(&parse_cst("~ 90", grammar::coord::coord2));
+        assert_eq!(rot, None);
+    }
+
+    #[test]
+    fn ranges() {
+        let range = |s| IntRange::from_cst(&parse_cst(s, grammar::range));
+        assert_eq!(
+            range("1..5"),
+            Some(IntRange {
+                min: Some(1),
+                max: Some(5)
+            })
+        );
+        assert_eq!(
+            range("..5"),
+            Some(IntRange {
+                min: None,
+                max: Some(5)
+            })
+        );
+        assert_eq!(
+            range("3.."),
+            Some(IntRange {
+                min: Some(3),
+                max: None
+            })
+        );
+        assert_eq!(
+            range("3"),
+            Some(IntRange {
+                min: Some(3),
+                max: Some(3)
+            })
+        );
+        assert_eq!(range("1.5..2"), None);
+        let range = FloatRange::from_cst(&parse_cst("1.5..2", grammar::range)).unwrap();
+        assert!(range.contains(1.75));
+        assert!(!range.contains(2.5));
+    }
+
+    #[test]
+    fn block_state() {
+        let state = BlockState::from_cst(&parse_cst(
+            "#beds[part=head]{Color:1b,Name:'a\\\\'}",
+            grammar::block::predicate,
+        ))
+        .unwrap();
+        assert_eq!(state.id.to_string(), "minecraft:beds");
+        assert!(state.tag);
+        assert_eq!(state.properties, vec![("part".into(), "head".into())]);
+        let mut nbt = BTreeMap::new();
+        nbt.insert("Color".into(), NbtValue::Byte(1));
+        nbt.insert("Name".into(), NbtValue::String("a\\".into()));
+        assert_eq!(state.nbt, Some(NbtValue::Compound(nbt)));
+
+        let missing = parse_cst("stone[facing=]", grammar::block::state);
+        assert_eq!(BlockState::from_cst(&missing), None);
+    }
+
+    #[test]
+    fn block_state_id() {
+        let reports = Reports::new().block("minecraft:stone", 1, &[]).block(
+            "minecraft:oak_stairs",
+            2,
+            &[
+                ("facing", &["north", "south"]),
+                ("half", &["top", "bottom"]),
+            ],
+        );
+        let id = |s| {
+            BlockState::from_cst(&parse_cst(s, grammar::block::predicate))
+                .unwrap()
+                .state_id(&reports)
+        };
+        assert_eq!(id("stone"), Some(1));
+        assert_eq!(id("oak_stairs[facing=north,half=top]"), Some(2));
+        assert_eq!(id("oak_stairs[half=bottom,facing=south]"), Some(5));
+        assert_eq!(id("oak_stairs[half=bottom]"), None);
+        assert_eq!(id("oak_stairs[half=bottom,half=top]"), None);
+        assert_eq!(id("oak_stairs[facing=east,half=top]"), None);
+        assert_eq!(id("#stairs"), None);
+        assert_eq!(id("dirt"), None);
+    }
+
+    #[test]
+    fn nbt() {
+        let nbt = |s| NbtValue::from_cst(&parse_cst(s, grammar::nbt::value));
+        assert_eq!(nbt("1"), Some(NbtValue::Int(1)));
+        assert_eq!(nbt("1.5"), Some(NbtValue::Double(1.5)));
+        assert_eq!(nbt("2f"), Some(NbtValue::Float(2.0)));
+        assert_eq!(
+            nbt("3000000000"),
+            Some(NbtValue::String("3000000000".into()))
+        );
+        assert_eq!(nbt("true"), Some(NbtValue::Byte(1)));
+        assert_eq!(nbt("[I;1,2]"), Some(NbtValue::IntArray(vec![1, 2])));
+        assert_eq!(
+            nbt("[1b,2b]"),
+            Some(NbtValue::List(vec![NbtValue::Byte(1), NbtValue::Byte(2)]))
+        );
+        assert_eq!(nbt("[1b,2]"), None);
+        assert_eq!(nbt("[B;1b,2]"), None);
+        assert_eq!(nbt("\"a\\\"b\""), Some(NbtValue::String("a\"b".into())));
+    }
+
+    #[test]
+    fn selector() {
+        let target = |s| EntityTarget::from_cst(&parse_cst(s, grammar::selector::entity));
+        assert_eq!(
+            target("@e[type=!pig,scores={a=1..},distance=..2.5]"),
+            Some(EntityTarget::Selector(EntitySelector {
+                kind: SelectorKind::AllEntities,
+                filters: vec![
+                    SelectorFilter {
+                        key: "type".into(),
+                        inverted: true,
+                        value: SelectorFilterValue::String("pig".into()),
+                    },
+                    SelectorFilter {
+                        key: "scores".into(),
+                        inverted: false,
+                        value: SelectorFilterValue::Map(vec![SelectorFilter {
+                            key: "a".into(),
+                            inverted: false,
+                            value: SelectorFilterValue::Range(FloatRange {
+                                min: Some(1.0),
+                                max: None,
+                            }),
+                        }]),
+                    },
+                    SelectorFilter {
+                        key: "distance".into(),
+                        inverted: false,
+                        value: SelectorFilterValue::Range(FloatRange {
+                            min: None,
+                            max: Some(2.5),
+                        }),
+                    },
+                ],
+            }))
+        );
+        assert_eq!(target("Steve"), Some(EntityTarget::Name("Steve".into())));
+        let uuid = match target("0-1-2-3-4") {
+            Some(EntityTarget::Uuid(uuid)) => uuid,
+            v => panic!("Expected a UUID, got {:?}", v),
+        };
+        assert_eq!(uuid.to_string(), "00000000-0001-0002-0003-000000000004");
+    }
+
+    #[test]
+    fn time_and_id() {
+        let time = Time::from_cst(&parse_cst("3s", grammar::time));
+        assert_eq!(time, Some(Time { ticks: 60 }));
+        let id: cst::ResourceLocation<_> = parse_cst("foo:bar/baz", grammar::resource_location);
+        assert_eq!(id.id(), Some(Identifier::new("foo", "bar/baz")));
+        let id: cst::ResourceLocation<_> = parse_cst("stone", grammar::resource_location);
+        assert_eq!(id.id().unwrap().to_string(), "minecraft:stone");
+    }
+}
@@ -1,5 +1,8 @@
 use mcfunction_parse::{
-    parser::StartInfo::{self, Skip},
+    parser::{
+        Progress,
+        StartInfo::{self, Skip},
+    },
     tokenset, TokenKind, TokenSet,
 };
 
@@ -10,7 +13,8 @@ use super::{
 };
 
 pub fn file(p: &mut Parser) {
-    loop {
+    let mut progress = Progress::new();
+    while p.progressed(&mut progress) {
         let mk = p.start(Item, Skip);
         doc_comments(p);
         let mut lk = p.lookahead();
@@ -42,10 +46,11 @@ pub fn file(p: &mut Parser) {
             minecraft_ident(p);
             if p.eat(LBracket) {
                 let bmk = p.start(DescribesBody, Skip);
-                while p.not_at(RBracket) {
+                let mut progress = Progress::new();
+                while p.not_at(RBracket) && p.progressed(&mut progress) {
                     minecraft_ident(p);
-                    if !p.at(RBracket) {
-                        p.expect(Comma);
+                    if !list_sep(p, Comma, RBracket) {
+                        break;
                     }
                 }
                 p.finish(bmk);
@@ -79,15 +84,16 @@ pub fn compound(p: &mut Parser) {
         p.finish(exmk);
     }
     p.expect(LCurly);
-    while p.not_at(RCurly) {
+    let mut progress = Progress::new();
+    while p.not_at(RCurly) && p.progressed(&mut progress) {
         let fmk = p.start(CompoundField, Skip);
         doc_comments(p);
         ident_or_qs(p);
         p.expect(Colon);
         field_type(p);
         p.finish(fmk);
-        if !p.at(RCurly) {
-            p.expect(Comma);
+        if !list_sep(p, Comma, RCurly) {
+            break;
         }
     }
     p.expect(RCurly);
@@ -116,7 +122,8 @@ pub fn enum_def(p: &mut Parser) {
     p.expect(RParen);
     p.expect(Ident);
     p.expect(LCurly);
-    while p.not_at(RCurly) {
+    let mut progress = Progress::new();
+    while p.not_at(RCurly) && p.progressed(&mut progress) {
         let mk = p.start(EnumEntry, Skip);
         doc_comments(p);
         p.expect(Ident);
@@ -128,8 +135,8 @@ pub fn enum_def(p: &mut Parser) {
             lk.add_errors();
         }
         p.finish(mk);
-        if !p.at(RCurly) {
-            p.expect(Comma);
+        if !list_sep(p, Comma, RCurly) {
+            break;
         }
     }
     p.expect(RCurly);
@@ -145,15 +152,16 @@ pub fn inject(p: &mut Parser) {
         p.bump();
         ident_path(p);
         p.expect(LCurly);
-        while p.not_at(RCurly) {
+        let mut progress = Progress::new();
+        while p.not_at(RCurly) && p.progressed(&mut progress) {
             let fmk = p.start(CompoundField, Skip);
             doc_comments(p);
             ident_or_qs(p);
             p.expect(Colon);
             field_type(p);
             p.finish(fmk);
-            if !p.at(RCurly) {
-                p.expect(Comma);
+            if !list_sep(p, Comma, RCurly) {
+                break;
             }
         }
         p.expect(RCurly);
@@ -177,7 +185,8 @@ pub fn inject(p: &mut Parser) {
         }
         ident_path(p);
         p.expect(LCurly);
-        while p.not_at(RCurly) {
+        let mut progress = Progress::new();
+        while p.not_at(RCurly) && p.progressed(&mut progress) {
             let mk = p.start(EnumEntry, Skip);
             doc_comments(p);
             p.expect(Ident);
@@ -189,8 +198,8 @@ pub fn inject(p: &mut Parser) {
                 lk.add_errors();
             }
             p.finish(mk);
-            if !p.at(RCurly) {
-                p.expect(Comma);
+            if !list_sep(p, Comma, RCurly) {
+                break;
             }
         }
         p.expect(RCurly);
@@ -200,6 +209,13 @@ pub fn inject(p: &mut Parser) {
     p.finish(mk);
 }
 
+// Expects the separator after an entry of a list that is closed by `close`.
+// Returns whether another entry follows. A missing separator skips a token instead,
+// unless it is a delimiter, so that the loop over the entries always makes progress.
+fn list_sep(p: &mut Parser, sep: NdTokenKind, close: NdTokenKind) -> bool {
+    p.at(close) || p.expect(sep) || !p.bump_recover(TokenSet::empty())
+}
+
 pub fn ident_path(p: &mut Parser) {
     let mk = p.start(IdentPath, StartInfo::None);
     p.eat(ColonColon);
@@ -246,7 +262,8 @@ pub fn registry_index(p: &mut Parser) {
     minecraft_ident(p);
     p.expect(LBracket);
     let fmk = p.start(FieldPath, StartInfo::None);
-    while p.not_at(RBracket) {
+    let mut progress = Progress::new();
+    while p.not_at(RBracket) && p.progressed(&mut progress) {
         let mut lk = p.lookahead();
         if lk.at(Ident) || lk.at(QuotedString) || lk.at(SuperKw) {
             p.bump();
@@ -254,8 +271,8 @@ pub fn registry_index(p: &mut Parser) {
             lk.add_errors();
             p.bump_recover(tokenset![Dot]);
         }
-        if !p.at(RBracket) {
-            p.expect(Dot);
+        if !list_sep(p, Dot, RBracket) {
+            break;
         }
     }
     p.finish(fmk);
@@ -303,10 +320,11 @@ pub fn field_type(p: &mut Parser) {
     } else if lk.at(LParen) {
         p.retype(&mk, UnionType, false);
         p.bump();
-        while p.not_at(RParen) {
+        let mut progress = Progress::new();
+        while p.not_at(RParen) && p.progressed(&mut progress) {
             field_type(p);
-            if !p.at(RParen) {
-                p.expect(Bar);
+            if !list_sep(p, Bar, RParen) {
+                break;
             }
         }
         p.expect(RParen);
@@ -350,3 +368,132 @@ pub fn doc_comments(p: &mut Parser) {
 fn index_over_ident(p: &Parser) -> bool {
     p.at(Colon) || p.nth(1) == Colon || (p.at(QuotedString) && p.nth(1) == LBracket)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::{group::NdGroupType, testing::parse, tokens::NdTokenKind, NbtdocLang};
+    use mcfunction_parse::{
+        ast::{AstView, Visit, Visitor, WalkEvent},
+        ParseError, SyntaxKind,
+    };
+
+    macro_rules! lossless_test {
+        ($name:ident, $e:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!(parse($e, super::file).root().to_string(), $e);
+            }
+        };
+    }
+
+    lossless_test!(empty, "");
+    lossless_test!(whitespace_only, "  \n\t\n");
+    lossless_test!(
+        compound,
+        "/// Doc\ncompound Foo extends ::bar::Baz {\n\tfield: int @ 0..5, // comment\n}\n\n"
+    );
+    lossless_test!(
+        enum_and_describes,
+        "enum(byte) Color { Red = 0, Blue = 1 }\r\n::Foo describes minecraft:entity[pig];   "
+    );
+    lossless_test!(unclosed, "compound Foo { a: [int\n");
+    lossless_test!(union_missing_bar, "compound A { b: (int string ;), c: (] }");
+    lossless_test!(
+        missing_separators,
+        "enum(int) E { A = 1 B = 2 ; C }\ncompound F { a: int ] b: int }\n::F describes a:b[c d;"
+    );
+
+    #[test]
+    fn union() {
+        let ast = parse("compound A { b: (int | string | [byte]) }", super::file);
+        assert!(!ast.root().walk().any(|evt| match evt {
+            WalkEvent::Enter(node) => matches!(node.kind(), SyntaxKind::Error(_)),
+            WalkEvent::Leave(_) => false,
+        }));
+    }
+
+    // Collects the names of the fields of every compound, in the order the compounds are left
+    #[derive(Default)]
+    struct FieldCollector<'a> {
+        current: Vec<&'a str>,
+        compounds: Vec<Vec<&'a str>>,
+        errors: usize,
+    }
+
+    impl<'a> Visitor<'a, &'a str, NbtdocLang> for FieldCollector<'a> {
+        fn visit_group(
+            &mut self,
+            node: AstView<'a, &'a str, NbtdocLang>,
+            kind: NdGroupType,
+        ) -> Visit {
+            if kind == NdGroupType::CompoundField {
+                let name = node
+                    .children()
+                    .find(|c| matches!(c.kind(), SyntaxKind::Token(NdTokenKind::Ident)));
+                self.current.extend(name.map(|n| n.string()));
+                Visit::SkipChildren
+            } else {
+                Visit::Continue
+            }
+        }
+
+        fn leave_group(&mut self, _: AstView<'a, &'a str, NbtdocLang>, kind: NdGroupType) {
+            if kind == NdGroupType::CompoundDef {
+                self.compounds.push(std::mem::take(&mut self.current));
+            }
+        }
+
+        fn visit_error(
+            &mut self,
+            _: AstView<'a, &'a str, NbtdocLang>,
+            _: &'a ParseError<NbtdocLang>,
+        ) -> Visit {
+            self.errors += 1;
+            Visit::Continue
+        }
+    }
+
+    #[test]
+    fn visitor() {
+        let src = "compound A { a: int, b: [string] }\ncompound B { c: A, d: [[byte] }";
+        let ast = parse(src, super::file);
+        let mut fields = FieldCollector::default();
+        ast.root().visit(&mut fields);
+        assert_eq!(fields.compounds, vec![vec!["a", "b"], vec!["c", "d"]]);
+        // The error in the field `d` is skipped with the rest of the field
+        assert_eq!(fields.errors, 0);
+        assert!(ast.root().walk().any(|evt| match evt {
+            WalkEvent::Enter(node) => matches!(node.kind(), SyntaxKind::Error(_)),
+            WalkEvent::Leave(_) => false,
+        }));
+    }
+
+    #[test]
+    fn walk_postorder() {
+        let ast = parse("mod a;\nuse ::b;", super::file);
+        let groups: Vec<_> = ast
+            .root()
+            .walk()
+            .filter_map(|evt| match evt {
+                WalkEvent::Leave(node) => match node.kind() {
+                    SyntaxKind::Root(g) | SyntaxKind::Group(g) | SyntaxKind::Joined(g) => Some(*g),
+                    _ => None,
+                },
+                WalkEvent::Enter(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                NdGroupType::DocCommentGroup,
+                NdGroupType::ModDecl,
+                NdGroupType::Item,
+                NdGroupType::DocCommentGroup,
+                NdGroupType::IdentPath,
+                NdGroupType::UseStatement,
+                NdGroupType::Item,
+                NdGroupType::File
+            ]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use mcfunction_parse::GroupType;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum NdGroupType {
     File,
@@ -38,3 +40,71 @@ pub enum NdGroupType {
 
     DescribesBody,
 }
+
+impl GroupType for NdGroupType {
+    fn description(self) -> &'static str {
+        use NdGroupType::*;
+
+        match self {
+            File => "an nbtdoc file",
+            Error => "valid syntax",
+            Item => "an item such as a compound, enum or statement",
+            IdentPath => "a path",
+            MinecraftIdent => "a resource location",
+            RegistryIndex => "a registry index",
+            FieldPath => "a field path",
+            Range => "a range",
+            ScalarType => "a scalar type",
+            ArrayType => "an array type",
+            ListType => "a list type",
+            IdType => "an id type",
+            UnionType => "a union type",
+            NamedType => "a named type",
+            IndexType => "an index type",
+            CompoundDef => "a compound definition",
+            CompoundExtends => "an extends clause",
+            CompoundField => "a compound field",
+            EnumDef => "an enum definition",
+            EnumEntry => "an enum entry",
+            ModDecl => "a module declaration",
+            UseStatement => "a use statement",
+            DescribesStatement => "a describes statement",
+            CompoundInject => "a compound injection",
+            EnumInject => "an enum injection",
+            DocCommentGroup => "doc comments",
+            DescribesBody => "the objects being described",
+        }
+    }
+
+    fn example(self) -> Option<&'static str> {
+        use NdGroupType::*;
+
+        Some(match self {
+            File | Error => return None,
+            Item | CompoundDef => "compound Foo { a: int }",
+            IdentPath => "::foo::Bar",
+            MinecraftIdent => "minecraft:entity",
+            RegistryIndex => "minecraft:entity[id]",
+            FieldPath => "super.id",
+            Range => "@ 0..5",
+            ScalarType => "int",
+            ArrayType => "byte[]",
+            ListType => "[string]",
+            IdType => "id(minecraft:item)",
+            UnionType => "(int | string)",
+            NamedType => "::foo::Bar",
+            IndexType => "minecraft:entity[id]",
+            CompoundExtends => "extends ::foo::Bar",
+            CompoundField => "a: int",
+            EnumDef => "enum(byte) Foo { Bar = 0 }",
+            EnumEntry => "Bar = 0",
+            ModDecl => "mod foo;",
+            UseStatement => "use ::foo::Bar;",
+            DescribesStatement => "Bar describes minecraft:entity[pig];",
+            CompoundInject => "inject compound ::foo::Bar { b: int }",
+            EnumInject => "inject enum byte ::foo::Baz { Qux = 1 }",
+            DocCommentGroup => "/// Documentation",
+            DescribesBody => "[pig, cow]",
+        })
+    }
+}
@@ -8,6 +8,7 @@ pub const PUNCT: &[(&str, NdTokenKind)] = &[
     (",", Comma),
     (":", Colon),
     ("@", At),
+    ("|", Bar),
     ("=", Eq),
     ("/", Slash),
     (".", Dot),
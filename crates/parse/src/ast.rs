@@ -1,10 +1,10 @@
 use crate::parser::Event;
-use crate::{parser::Language, LineCol, ParseError, Span};
+use crate::{parser::Language, Diagnostic, LineCol, ParseError, Span};
 use std::convert::From;
 use std::marker::PhantomData;
 use std::{
     iter::{DoubleEndedIterator, ExactSizeIterator},
-    ops::Deref,
+    ops::{self, Deref},
 };
 use util::arena::{Arena, RawId};
 
@@ -23,6 +23,8 @@ pub struct Ast<T: AsRef<str>, L: Language> {
     src: T,
     arena: Arena<InnerAstIndex, InnerAstNode<L>>,
     errors: Vec<InnerAstIndex>,
+    // Number of nodes in the arena that are no longer reachable from the root
+    garbage: usize,
 }
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq)]
@@ -46,10 +48,78 @@ impl<T: AsRef<str>, L: Language> Ast<T, L> {
         }
     }
 
+    pub fn src(&self) -> &str {
+        self.src.as_ref()
+    }
+
     pub fn errors(&self) -> impl Iterator<Item = AstView<T, L>> + '_ {
         self.errors.iter().copied().map(move |v| AstView(v, self))
     }
 
+    /// Gets a [`Diagnostic`] for every error in the tree, in source order.
+    ///
+    /// [`Diagnostic`]: ../struct.Diagnostic.html
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut out: Vec<Diagnostic> = self.errors().filter_map(Diagnostic::from_error).collect();
+        out.sort_by_key(|d| d.span().start());
+        out
+    }
+
+    /// Finds the tokens that touch the position `pos`.
+    ///
+    /// A position on the boundary between two tokens touches both of them,
+    /// and a position past the end of the source touches nothing.
+    /// Error nodes are never returned, because they are not tokens.
+    pub fn token_at_offset(&self, pos: LineCol) -> TokenAtOffset<AstView<'_, T, L>> {
+        let mut found = vec![];
+        let mut walker = self.root().walk();
+        while let Some(evt) = walker.next() {
+            let node = match evt {
+                WalkEvent::Enter(node) => node,
+                WalkEvent::Leave(_) => continue,
+            };
+            let span = node.span();
+            match node.kind() {
+                SyntaxKind::Root(_) => {}
+                SyntaxKind::Token(_) if span.start() <= pos && pos <= span.end() => {
+                    found.push(node)
+                }
+                SyntaxKind::Group(_) | SyntaxKind::Joined(_)
+                    if span.start() <= pos && pos <= span.end() => {}
+                _ => walker.skip_children(),
+            }
+            if found.len() == 2 || span.start() > pos {
+                break;
+            }
+        }
+        match found[..] {
+            [] => TokenAtOffset::None,
+            [tk] => TokenAtOffset::Single(tk),
+            [left, right] => TokenAtOffset::Between(left, right),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Finds the smallest node that contains all of `span`.
+    ///
+    /// If more than one child of a node contains the span,
+    /// such as when an empty span is on the boundary between two tokens,
+    /// that node is the result. Error nodes are never returned.
+    pub fn covering_element(&self, span: Span) -> AstView<'_, T, L> {
+        let mut node = self.root();
+        loop {
+            let mut containing = node.children().filter(|c| {
+                !matches!(c.kind(), SyntaxKind::Error(_))
+                    && c.span().start() <= span.start()
+                    && span.end() <= c.span().end()
+            });
+            match (containing.next(), containing.next()) {
+                (Some(child), None) => node = child,
+                _ => return node,
+            }
+        }
+    }
+
     pub fn retype_src<I: From<T> + AsRef<str>>(self) -> Ast<I, L> {
         self.retype_src_with(I::from)
     }
@@ -59,10 +129,180 @@ impl<T: AsRef<str>, L: Language> Ast<T, L> {
             root: self.root,
             arena: self.arena,
             errors: self.errors,
+            garbage: self.garbage,
             src: f(self.src),
         }
     }
 
+    /// Replaces the children of the root in `range` with the children of the root of `other`.
+    ///
+    /// `other` must have been built from the part of `src` that begins at `start`,
+    /// given as a byte offset and its position. Everything in this tree that comes after `old_end`
+    /// is moved so that it comes after `new_end` instead.
+    ///
+    /// Moving the rest of the tree visits every node after the edit, so the cost grows with
+    /// the size of the tree behind `range`, not only with the size of `other`.
+    ///
+    /// This is the building block of incremental reparsing for the language crates,
+    /// and is not meant to be called directly. Use a language's `reparse` instead,
+    /// such as `CommandParser::reparse`.
+    #[doc(hidden)]
+    pub fn splice<S, I>(
+        mut self,
+        range: ops::Range<usize>,
+        other: Ast<S, L>,
+        src: I,
+        start: (usize, LineCol),
+        old_end: (usize, LineCol),
+        new_end: (usize, LineCol),
+    ) -> Ast<I, L>
+    where
+        S: AsRef<str>,
+        I: AsRef<str>,
+    {
+        let root = self.root;
+
+        let mut before = vec![];
+        let mut after = vec![];
+        for err in self.errors.drain(..) {
+            let mut top = err;
+            while let Some(parent) = self.arena[top].parent {
+                if parent == root {
+                    break;
+                }
+                top = parent;
+            }
+            let ind = self.arena[top].sibling_index;
+            if ind < range.start {
+                before.push(err);
+            } else if ind >= range.end {
+                after.push(err);
+            }
+        }
+
+        let mut stack = self.arena[root].children[range.clone()].to_vec();
+        while let Some(ind) = stack.pop() {
+            self.garbage += 1;
+            stack.extend_from_slice(&self.arena[ind].children);
+        }
+
+        if old_end != new_end {
+            let mut stack = self.arena[root].children[range.end..].to_vec();
+            while let Some(ind) = stack.pop() {
+                let node = &mut self.arena[ind];
+                node.span = Span::new(
+                    shift_after(node.span.start(), old_end.1, new_end.1),
+                    shift_after(node.span.end(), old_end.1, new_end.1),
+                );
+                let shift = |off: usize| {
+                    if off >= old_end.0 {
+                        off - old_end.0 + new_end.0
+                    } else {
+                        off
+                    }
+                };
+                node.string = (shift(node.string.0), shift(node.string.1));
+                stack.extend_from_slice(&node.children);
+            }
+        }
+
+        let other_top = other.arena[other.root].children.clone();
+        let mut map = Vec::with_capacity(other.arena.len());
+        for (i, mut node) in other.arena.into_iter().enumerate() {
+            if i == usize::from(RawId::from(other.root)) {
+                map.push(root);
+                continue;
+            }
+            node.span = Span::new(
+                shift_by(node.span.start(), start.1),
+                shift_by(node.span.end(), start.1),
+            );
+            node.string = (node.string.0 + start.0, node.string.1 + start.0);
+            map.push(self.arena.push(node));
+        }
+        let remap = |ind: InnerAstIndex| map[usize::from(RawId::from(ind))];
+        for new in map.iter().copied() {
+            if new == root {
+                continue;
+            }
+            let node = &mut self.arena[new];
+            node.parent = node.parent.map(remap);
+            for child in &mut node.children {
+                *child = remap(*child);
+            }
+            if node.parent == Some(root) {
+                node.sibling_index += range.start;
+            }
+        }
+
+        let top: Vec<_> = other_top.into_iter().map(remap).collect();
+        let len = top.len();
+        self.arena[root].children.splice(range.clone(), top);
+        let children = self.arena[root].children[range.start + len..].to_vec();
+        for (i, child) in children.into_iter().enumerate() {
+            self.arena[child].sibling_index = range.start + len + i;
+        }
+
+        before.extend(other.errors.into_iter().map(remap));
+        before.extend(after);
+        self.errors = before;
+
+        if self.garbage > self.arena.len() - self.garbage {
+            self.compact();
+        }
+        self.set_root_span(src.as_ref().len());
+        self.retype_src_with(|_| src)
+    }
+
+    // The root spans the whole source, which ends where its last child ends
+    fn set_root_span(&mut self, len: usize) {
+        let end = self.arena[self.root]
+            .children
+            .iter()
+            .rev()
+            .find(|c| !matches!(self.arena[**c].kind, SyntaxKind::Error(_)))
+            .map_or_else(LineCol::default, |c| self.arena[*c].span.end());
+        let root = &mut self.arena[self.root];
+        root.span = Span::new(LineCol::default(), end);
+        root.string = (0, len);
+    }
+
+    // Rebuilds the arena so it only contains nodes that are reachable from the root
+    fn compact(&mut self) {
+        let mut map = vec![None; self.arena.len()];
+        let mut arena = Arena::new();
+        let root = arena.push(InnerAstNode {
+            children: vec![],
+            ..self.arena[self.root].clone()
+        });
+        map[usize::from(RawId::from(self.root))] = Some(root);
+        let mut stack: Vec<_> = self.arena[self.root]
+            .children
+            .iter()
+            .rev()
+            .map(|&c| (c, root))
+            .collect();
+        while let Some((old, parent)) = stack.pop() {
+            let node = &self.arena[old];
+            let new = arena.push(InnerAstNode {
+                children: vec![],
+                parent: Some(parent),
+                ..node.clone()
+            });
+            arena[parent].children.push(new);
+            map[usize::from(RawId::from(old))] = Some(new);
+            stack.extend(node.children.iter().rev().map(|&c| (c, new)));
+        }
+        self.errors = self
+            .errors
+            .iter()
+            .map(|&e| map[usize::from(RawId::from(e))].unwrap())
+            .collect();
+        self.arena = arena;
+        self.root = root;
+        self.garbage = 0;
+    }
+
     pub fn view_index(&self, ind: AstIndex<L>) -> AstView<T, L> {
         AstView(ind.0, self)
     }
@@ -173,7 +413,12 @@ where
     {
         let view = self.view();
         let cind = view.node().sibling_index;
-        let id = view.children().skip(cind).find(|c| C::can_cast(*c))?.0;
+        let id = view
+            .parent()?
+            .children()
+            .skip(cind + 1)
+            .find(|c| C::can_cast(*c))?
+            .0;
         Some(C::new(OwnedNode(id, self.1)))
     }
 
@@ -184,8 +429,9 @@ where
         let view = self.view();
         let cind = view.node().sibling_index;
         let id = view
+            .parent()?
             .children()
-            .take(cind + 1)
+            .take(cind)
             .rev()
             .find(|c| C::can_cast(*c))?
             .0;
@@ -221,16 +467,21 @@ where
 
 impl<C, D, T, L> Iterator for Children<C, D, T, L>
 where
-    C: CstNode<Node = OwnedNode<D>>,
+    C: CstNode<String = T, Language = L, Node = OwnedNode<D>>,
     T: AsRef<str>,
     L: Language,
     D: Deref<Target = Ast<T, L>> + Clone,
 {
     type Item = C;
     fn next(&mut self) -> Option<Self::Item> {
-        let child = self.ast.arena[self.children].children.get(self.index)?;
-        self.index += 1;
-        Some(CstNode::new(OwnedNode(*child, self.ast.clone())))
+        // Skip the children that are not of type `C`, such as whitespace
+        loop {
+            let child = *self.ast.arena[self.children].children.get(self.index)?;
+            self.index += 1;
+            if C::can_cast(AstView(child, &*self.ast)) {
+                return Some(CstNode::new(OwnedNode(child, self.ast.clone())));
+            }
+        }
     }
 }
 
@@ -261,6 +512,16 @@ where
         self.node().span
     }
 
+    /// Gets the byte range of the node in the source.
+    pub fn range(&self) -> ops::Range<usize> {
+        let (start, end) = self.node().string;
+        start..end
+    }
+
+    pub(crate) fn ast(&self) -> &'a Ast<T, L> {
+        self.1
+    }
+
     pub fn parent(self) -> Option<Self> {
         self.node().parent.map(|v| self.new_view(v))
     }
@@ -276,6 +537,11 @@ where
             .map(move |v| self.new_view(v))
     }
 
+    /// Iterates over this node and then all of its ancestors, ending at the root.
+    pub fn ancestors(self) -> impl Iterator<Item = AstView<'a, T, L>> {
+        std::iter::successors(Some(self), |v| v.parent())
+    }
+
     pub fn first_child(self) -> Option<AstView<'a, T, L>> {
         self.node()
             .children
@@ -318,6 +584,44 @@ where
         self.parent()?.nth_child(ind)
     }
 
+    /// Walks the subtree of this node, see [`Walker`].
+    ///
+    /// [`Walker`]: ./struct.Walker.html
+    pub fn walk(self) -> Walker<'a, T, L> {
+        Walker {
+            root: self,
+            next: Some(WalkEvent::Enter(self)),
+            last: None,
+        }
+    }
+
+    /// Walks the subtree of this node, calling the methods of `visitor`
+    /// for every node that is entered and left.
+    pub fn visit<V: Visitor<'a, T, L>>(self, visitor: &mut V) {
+        let mut walker = self.walk();
+        while let Some(evt) = walker.next() {
+            let visit = match evt {
+                WalkEvent::Enter(node) => match node.kind() {
+                    SyntaxKind::Root(g) | SyntaxKind::Group(g) => visitor.visit_group(node, *g),
+                    SyntaxKind::Joined(g) => visitor.visit_joined(node, *g),
+                    SyntaxKind::Token(tk) => visitor.visit_token(node, *tk),
+                    SyntaxKind::Error(err) => visitor.visit_error(node, err),
+                },
+                WalkEvent::Leave(node) => {
+                    match node.kind() {
+                        SyntaxKind::Root(g) | SyntaxKind::Group(g) => visitor.leave_group(node, *g),
+                        SyntaxKind::Joined(g) => visitor.leave_joined(node, *g),
+                        SyntaxKind::Token(_) | SyntaxKind::Error(_) => {}
+                    }
+                    Visit::Continue
+                }
+            };
+            if let Visit::SkipChildren = visit {
+                walker.skip_children();
+            }
+        }
+    }
+
     fn new_view(self, idx: InnerAstIndex) -> AstView<'a, T, L> {
         AstView(idx, self.1)
     }
@@ -327,6 +631,174 @@ where
     }
 }
 
+/// The tokens found by [`Ast::token_at_offset`].
+///
+/// [`Ast::token_at_offset`]: ./struct.Ast.html#method.token_at_offset
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TokenAtOffset<V> {
+    /// No token touches the position.
+    None,
+    /// The position is inside of a token, or touches exactly one token.
+    Single(V),
+    /// The position is on the boundary between two tokens, the left one is first.
+    Between(V, V),
+}
+
+impl<V> TokenAtOffset<V> {
+    /// Applies `f` to every token.
+    pub fn map<F: FnMut(V) -> U, U>(self, mut f: F) -> TokenAtOffset<U> {
+        match self {
+            TokenAtOffset::None => TokenAtOffset::None,
+            TokenAtOffset::Single(v) => TokenAtOffset::Single(f(v)),
+            TokenAtOffset::Between(l, r) => TokenAtOffset::Between(f(l), f(r)),
+        }
+    }
+
+    /// Gets the token that the position is in,
+    /// preferring the token that ends at the position when on a boundary.
+    pub fn left_biased(self) -> Option<V> {
+        match self {
+            TokenAtOffset::None => None,
+            TokenAtOffset::Single(v) | TokenAtOffset::Between(v, _) => Some(v),
+        }
+    }
+
+    /// Gets the token that the position is in,
+    /// preferring the token that starts at the position when on a boundary.
+    pub fn right_biased(self) -> Option<V> {
+        match self {
+            TokenAtOffset::None => None,
+            TokenAtOffset::Single(v) | TokenAtOffset::Between(_, v) => Some(v),
+        }
+    }
+}
+
+/// An event of a [`Walker`].
+///
+/// [`Walker`]: ./struct.Walker.html
+#[derive(Debug, PartialEq, Eq)]
+pub enum WalkEvent<'a, T: AsRef<str>, L: Language> {
+    /// The node is reached, before any of its children.
+    Enter(AstView<'a, T, L>),
+    /// All of the children of the node have been walked.
+    Leave(AstView<'a, T, L>),
+}
+
+impl<'a, T: AsRef<str>, L: Language> Copy for WalkEvent<'a, T, L> {}
+impl<'a, T: AsRef<str>, L: Language> Clone for WalkEvent<'a, T, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// An iterator over the nodes of a subtree in source order.
+///
+/// Every node produces an [`Enter`] event, then the events of its children,
+/// and then a [`Leave`] event. Filtering for `Enter` gives a preorder traversal,
+/// and filtering for `Leave` gives a postorder traversal.
+/// Tokens and errors have no children, so their `Leave` directly follows their `Enter`.
+///
+/// The walk does not recurse, so it can be used on trees of any depth.
+///
+/// [`Enter`]: ./enum.WalkEvent.html#variant.Enter
+/// [`Leave`]: ./enum.WalkEvent.html#variant.Leave
+pub struct Walker<'a, T: AsRef<str>, L: Language> {
+    root: AstView<'a, T, L>,
+    next: Option<WalkEvent<'a, T, L>>,
+    last: Option<WalkEvent<'a, T, L>>,
+}
+
+impl<'a, T: AsRef<str>, L: Language> Walker<'a, T, L> {
+    /// Skips the children of the node that was just entered.
+    ///
+    /// The next event will be the `Leave` of that node.
+    /// Does nothing if the last event was not an `Enter`.
+    pub fn skip_children(&mut self) {
+        if let Some(WalkEvent::Enter(node)) = self.last {
+            self.next = Some(WalkEvent::Leave(node));
+        }
+    }
+}
+
+impl<'a, T: AsRef<str>, L: Language> Iterator for Walker<'a, T, L> {
+    type Item = WalkEvent<'a, T, L>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let evt = self.next.take()?;
+        self.next = match evt {
+            WalkEvent::Enter(node) => Some(match node.first_child() {
+                Some(child) => WalkEvent::Enter(child),
+                None => WalkEvent::Leave(node),
+            }),
+            WalkEvent::Leave(node) if node == self.root => None,
+            WalkEvent::Leave(node) => match node.next_sibling() {
+                Some(sibling) => Some(WalkEvent::Enter(sibling)),
+                None => node.parent().map(WalkEvent::Leave),
+            },
+        };
+        self.last = Some(evt);
+        Some(evt)
+    }
+}
+
+/// Whether a [`Visitor`] should visit the children of a node.
+///
+/// [`Visitor`]: ./trait.Visitor.html
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Visit {
+    Continue,
+    SkipChildren,
+}
+
+/// Callbacks for each kind of node, used with [`AstView::visit`].
+///
+/// Every method does nothing by default, so an implementation
+/// only needs to handle the nodes that it is interested in.
+/// The root is visited as a group.
+///
+/// [`AstView::visit`]: ./struct.AstView.html#method.visit
+pub trait Visitor<'a, T: AsRef<str>, L: Language> {
+    fn visit_group(&mut self, _node: AstView<'a, T, L>, _kind: L::GroupType) -> Visit {
+        Visit::Continue
+    }
+
+    fn leave_group(&mut self, _node: AstView<'a, T, L>, _kind: L::GroupType) {}
+
+    fn visit_joined(&mut self, _node: AstView<'a, T, L>, _kind: L::GroupType) -> Visit {
+        Visit::Continue
+    }
+
+    fn leave_joined(&mut self, _node: AstView<'a, T, L>, _kind: L::GroupType) {}
+
+    fn visit_token(&mut self, _node: AstView<'a, T, L>, _kind: L::TokenKind) -> Visit {
+        Visit::Continue
+    }
+
+    fn visit_error(&mut self, _node: AstView<'a, T, L>, _err: &'a ParseError<L>) -> Visit {
+        Visit::Continue
+    }
+}
+
+// Moves a position that is relative to `base` so it is relative to the start of the source
+fn shift_by(pos: LineCol, base: LineCol) -> LineCol {
+    if pos.line() == 0 {
+        LineCol::new(base.line(), base.col() + pos.col())
+    } else {
+        LineCol::new(base.line() + pos.line(), pos.col())
+    }
+}
+
+// Moves a position at or after `old` so it has the same position relative to `new`
+fn shift_after(pos: LineCol, old: LineCol, new: LineCol) -> LineCol {
+    if pos < old {
+        pos
+    } else if pos.line() == old.line() {
+        LineCol::new(new.line(), new.col() + pos.col() - old.col())
+    } else {
+        LineCol::new(pos.line() - old.line() + new.line(), pos.col())
+    }
+}
+
 pub fn build_ast<T: AsRef<str>, L: Language>(
     events: Vec<Event<L>>,
     src: T,
@@ -416,12 +888,15 @@ pub fn build_ast<T: AsRef<str>, L: Language>(
         }
     }
 
+    let len = src.as_ref().len();
     let mut out = Ast {
         arena,
         errors: Vec::new(),
+        garbage: 0,
         root,
         src,
     };
+    out.set_root_span(len);
 
     'out: for err in &errors {
         let mut view = AstView(*err, &out);
@@ -454,6 +929,26 @@ impl<'a, T: AsRef<str>, L: Language> Clone for AstView<'a, T, L> {
         *self
     }
 }
+/// Writes the source text of the node.
+///
+/// Every token of the source is a leaf of the tree,
+/// so displaying the root reproduces the source exactly.
+impl<'a, T, L> std::fmt::Display for AstView<'a, T, L>
+where
+    T: AsRef<str>,
+    L: Language,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for evt in self.walk() {
+            if let WalkEvent::Enter(node) = evt {
+                if let SyntaxKind::Token(_) = node.kind() {
+                    f.write_str(node.string())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
 impl<'a, T, L> std::fmt::Debug for AstView<'a, T, L>
 where
     T: AsRef<str>,
@@ -0,0 +1,299 @@
+//! Typed wrappers around the nodes of an [`Ast`], generated by the [`cst!`] macro.
+//!
+//! [`Ast`]: ../struct.Ast.html
+//! [`cst!`]: ../macro.cst.html
+
+use std::ops::Deref;
+
+/// A borrowed [`Ast`] that a [`RefNode`] can hold instead of a clone of the owning pointer.
+///
+/// [`Ast`]: ../struct.Ast.html
+/// [`RefNode`]: ../macro.cst.html
+#[derive(Debug, PartialEq, Eq)]
+pub struct DerefRef<'a, D>(pub &'a D);
+
+impl<'a, D> Deref for DerefRef<'a, D>
+where
+    D: Deref,
+{
+    type Target = D::Target;
+    fn deref(&self) -> &Self::Target {
+        self.0.deref()
+    }
+}
+impl<'a, T> Clone for DerefRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, T> Copy for DerefRef<'a, T> {}
+
+/// Generates typed CST nodes for a [`Language`] from a description of the nodes.
+///
+/// The description starts with the language and its group and token types,
+/// which must be in scope. Along with the nodes, the macro defines:
+/// - `Node`, a node that owns an `Arc` of the tree.
+/// - `RefNode<'a>`, a node that borrows the tree.
+/// - `NH`, a trait for both of them that the generated nodes are generic over.
+///
+/// Every node is matched by a `root`, `group`, `joined` or `token` kind.
+/// Structs can list accessors for their children, which find the `first` or
+/// `last` child of a type, or iterate over all the `children` of a type.
+/// Enums match any of the kinds of their variants, and can have a getter
+/// that converts a variant into a struct.
+///
+/// ```ignore
+/// mcfunction_parse::cst! {
+///     lang = McfLang, group = McGroupType, token = McTokenKind;
+///
+///     /// A list of NBT values
+///     pub struct NbtSequence: group NbtSequence {
+///         pub fn entries: children NbtValue;
+///     }
+///
+///     pub enum NbtValue {
+///         Compound = group NbtCompound => compound: NbtCompound,
+///         Sequence = group NbtSequence => sequence: NbtSequence,
+///     }
+///
+///     struct Semicolon: token Semicolon;
+/// }
+/// ```
+///
+/// Nodes that need more than that can be written by hand next to the generated ones,
+/// and generated structs can get more methods in another `impl` block.
+///
+/// [`Language`]: ./parser/trait.Language.html
+#[macro_export]
+macro_rules! cst {
+    (
+        lang = $lang:ident, group = $group:ident, token = $token:ident;
+        $($items:tt)*
+    ) => {
+        pub type Node = $crate::ast::OwnedNode<
+            ::std::sync::Arc<$crate::Ast<::std::sync::Arc<str>, $lang>>
+        >;
+        pub type RefNode<'a> = $crate::ast::OwnedNode<
+            $crate::cst::DerefRef<'a, ::std::sync::Arc<$crate::Ast<::std::sync::Arc<str>, $lang>>>
+        >;
+
+        /// The nodes that a typed CST node can hold.
+        pub trait NH {
+            fn into_arc(self) -> Node;
+            fn as_ref(&self) -> RefNode;
+            fn view(&self) -> $crate::AstView<::std::sync::Arc<str>, $lang>;
+        }
+        impl NH for Node {
+            fn into_arc(self) -> Node {
+                self
+            }
+
+            fn as_ref(&self) -> RefNode {
+                self.borrow(|v| $crate::cst::DerefRef(v))
+            }
+
+            fn view(&self) -> $crate::AstView<::std::sync::Arc<str>, $lang> {
+                self.view()
+            }
+        }
+        impl<'a> NH for RefNode<'a> {
+            fn into_arc(self) -> Node {
+                self.convert(|d| d.0.clone())
+            }
+
+            fn as_ref(&self) -> RefNode {
+                self.borrow(|d| *d)
+            }
+
+            fn view(&self) -> $crate::AstView<::std::sync::Arc<str>, $lang> {
+                self.view()
+            }
+        }
+
+        $crate::cst!(@items ($lang $group $token) $($items)*);
+    };
+
+    (@items $hdr:tt) => {};
+    (@items $hdr:tt
+        $(#[$m:meta])*
+        $vis:vis struct $id:ident : $kty:ident $kid:ident {
+            $( $(#[$am:meta])* $avis:vis fn $aname:ident : $how:ident $ret:ident ; )*
+        }
+        $($rest:tt)*
+    ) => {
+        $(#[$m])*
+        #[derive(Debug, PartialEq, Eq)]
+        $vis struct $id<N>(N);
+        $crate::cst!(@node $hdr $id $kty $kid);
+        impl<N: NH> $id<N> {
+            $( $crate::cst!(@accessor [$(#[$am])*] $avis $aname $how $ret); )*
+        }
+        $crate::cst!(@items $hdr $($rest)*);
+    };
+    (@items $hdr:tt
+        $(#[$m:meta])*
+        $vis:vis struct $id:ident : $kty:ident $kid:ident ;
+        $($rest:tt)*
+    ) => {
+        $(#[$m])*
+        #[derive(Debug, PartialEq, Eq)]
+        $vis struct $id<N>(N);
+        $crate::cst!(@node $hdr $id $kty $kid);
+        $crate::cst!(@items $hdr $($rest)*);
+    };
+    (@items ($lang:ident $group:ident $token:ident)
+        $(#[$m:meta])*
+        $vis:vis enum $id:ident {
+            $( $(#[$vm:meta])* $nid:ident = $kty:ident $kid:ident $(=> $gfn:ident : $ret:ident)? ),+ $(,)?
+        }
+        $($rest:tt)*
+    ) => {
+        $(#[$m])*
+        #[derive(Debug, PartialEq, Eq)]
+        $vis enum $id<N> {
+            $( $(#[$vm])* $nid(N) ),+
+        }
+
+        impl<N: NH> $crate::ast::CstNode for $id<N> {
+            type String = ::std::sync::Arc<str>;
+            type Language = $lang;
+            type Node = N;
+
+            fn can_cast(value: $crate::AstView<::std::sync::Arc<str>, $lang>) -> bool {
+                match value.kind() {
+                    $( $crate::cst!(@kind ($lang $group $token) $kty $kid) )|+ => true,
+                    _ => false,
+                }
+            }
+
+            fn view(&self) -> $crate::AstView<::std::sync::Arc<str>, $lang> {
+                match self {
+                    $( Self::$nid(v) => v.view() ),+
+                }
+            }
+
+            fn into_node(self) -> Self::Node {
+                match self {
+                    $( Self::$nid(v) => v ),+
+                }
+            }
+
+            fn new(node: N) -> Self {
+                match node.view().kind() {
+                    $( $crate::cst!(@kind ($lang $group $token) $kty $kid) => Self::$nid(node), )+
+                    _ => panic!("Invalid node type"),
+                }
+            }
+        }
+
+        impl<N: NH> $id<N> {
+            $( $(
+                pub fn $gfn(self) -> Option<$ret<N>> {
+                    use $crate::ast::CstNode;
+                    match self {
+                        Self::$nid(v) => if $ret::<N>::can_cast(v.view()) {
+                            Some($ret::new(v))
+                        } else {
+                            None
+                        },
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+            )? )+
+
+            #[allow(dead_code)]
+            pub fn into_arc(self) -> $id<Node> {
+                use $crate::ast::CstNode;
+                $id::<Node>::new(self.into_node().into_arc())
+            }
+
+            #[allow(dead_code)]
+            pub fn as_ref(&self) -> $id<RefNode> {
+                use $crate::ast::CstNode;
+                $id::<RefNode>::new(match self {
+                    $( Self::$nid(v) => v.as_ref() ),+
+                })
+            }
+        }
+
+        $crate::cst!(@items ($lang $group $token) $($rest)*);
+    };
+
+    (@node ($lang:ident $group:ident $token:ident) $id:ident $kty:ident $kid:ident) => {
+        impl<N: NH> $crate::ast::CstNode for $id<N> {
+            type String = ::std::sync::Arc<str>;
+            type Language = $lang;
+            type Node = N;
+
+            fn can_cast(value: $crate::AstView<::std::sync::Arc<str>, $lang>) -> bool {
+                match value.kind() {
+                    $crate::cst!(@kind ($lang $group $token) $kty $kid) => true,
+                    _ => false,
+                }
+            }
+
+            fn view(&self) -> $crate::AstView<::std::sync::Arc<str>, $lang> {
+                self.0.view()
+            }
+
+            fn into_node(self) -> Self::Node {
+                self.0
+            }
+
+            fn new(node: N) -> Self {
+                Self(node)
+            }
+        }
+
+        impl<N: NH> $id<N> {
+            /// Gets the text of the node.
+            #[allow(dead_code)]
+            pub fn string(&self) -> &str {
+                self.0.view().string()
+            }
+
+            #[allow(dead_code)]
+            pub fn into_arc(self) -> $id<Node> {
+                $id(self.0.into_arc())
+            }
+
+            #[allow(dead_code)]
+            pub fn as_ref(&self) -> $id<RefNode> {
+                $id(self.0.as_ref())
+            }
+        }
+    };
+
+    (@kind ($lang:ident $group:ident $token:ident) root $kid:ident) => {
+        $crate::SyntaxKind::Root($group::$kid)
+    };
+    (@kind ($lang:ident $group:ident $token:ident) group $kid:ident) => {
+        $crate::SyntaxKind::Group($group::$kid)
+    };
+    (@kind ($lang:ident $group:ident $token:ident) joined $kid:ident) => {
+        $crate::SyntaxKind::Joined($group::$kid)
+    };
+    (@kind ($lang:ident $group:ident $token:ident) token $kid:ident) => {
+        $crate::SyntaxKind::Token($token::$kid)
+    };
+
+    (@accessor [$($am:tt)*] $avis:vis $aname:ident first $ret:ident) => {
+        $($am)*
+        $avis fn $aname(&self) -> Option<$ret<RefNode>> {
+            self.0.as_ref().first_child()
+        }
+    };
+    (@accessor [$($am:tt)*] $avis:vis $aname:ident last $ret:ident) => {
+        $($am)*
+        $avis fn $aname(&self) -> Option<$ret<RefNode>> {
+            self.0.as_ref().last_child()
+        }
+    };
+    (@accessor [$($am:tt)*] $avis:vis $aname:ident children $ret:ident) => {
+        $($am)*
+        $avis fn $aname(&self) -> impl Iterator<Item = $ret<RefNode>> + '_ {
+            self.0.as_ref().children()
+        }
+    };
+}
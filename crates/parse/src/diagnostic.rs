@@ -0,0 +1,363 @@
+use crate::{
+    ast::{AstView, SyntaxKind},
+    parser::Language,
+    LineIndex, Span,
+};
+
+use std::fmt::{self, Display, Formatter};
+
+/// How serious a [`Diagnostic`] is.
+///
+/// [`Diagnostic`]: ./struct.Diagnostic.html
+#[derive(Copy, Clone, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A message attached to a region of the source.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Label {
+    span: Span,
+    message: String,
+    primary: bool,
+}
+
+impl Label {
+    /// Constructs the label that marks the main location of a diagnostic.
+    pub fn primary(span: Span, message: impl Into<String>) -> Self {
+        Label {
+            span,
+            message: message.into(),
+            primary: true,
+        }
+    }
+
+    /// Constructs a label that marks a location related to a diagnostic.
+    pub fn secondary(span: Span, message: impl Into<String>) -> Self {
+        Label {
+            span,
+            message: message.into(),
+            primary: false,
+        }
+    }
+
+    /// Gets the span that the label points to.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the message of the label, which may be empty.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets whether this label marks the main location of the diagnostic.
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+}
+
+/// A problem found in a source file.
+///
+/// Diagnostics are plain data, so they can be collected, filtered and
+/// sorted before they are shown. Use [`display`] to render one
+/// together with the source it refers to.
+///
+/// [`display`]: #method.display
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    span: Span,
+    labels: Vec<Label>,
+    notes: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Constructs a diagnostic with a message and a primary span.
+    ///
+    /// The diagnostic starts without any labels,
+    /// so only the span is underlined when it is rendered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ Diagnostic, LineCol, Severity, Span };
+    ///
+    /// let span = Span::new(LineCol::new(0, 4), LineCol::new(0, 9));
+    /// let diag = Diagnostic::new(Severity::Warning, "unused argument", span);
+    /// assert_eq!(diag.span(), span);
+    /// ```
+    pub fn new(severity: Severity, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity,
+            message: message.into(),
+            span,
+            labels: vec![],
+            notes: vec![],
+        }
+    }
+
+    /// Constructs an error diagnostic.
+    pub fn error(message: impl Into<String>, span: Span) -> Self {
+        Self::new(Severity::Error, message, span)
+    }
+
+    /// Constructs a warning diagnostic.
+    pub fn warning(message: impl Into<String>, span: Span) -> Self {
+        Self::new(Severity::Warning, message, span)
+    }
+
+    /// Constructs a diagnostic from an error node of an [`Ast`].
+    ///
+    /// Returns `None` if the node is not an error.
+    ///
+    /// [`Ast`]: ./ast/struct.Ast.html
+    pub fn from_error<T: AsRef<str>, L: Language>(view: AstView<T, L>) -> Option<Self> {
+        let err = match view.kind() {
+            SyntaxKind::Error(err) => err,
+            _ => return None,
+        };
+        let span = view.span();
+        let found = view.string().lines().next().unwrap_or("").trim();
+        let label = if span.start() == span.end() || found.is_empty() {
+            Label::primary(span, "expected here")
+        } else {
+            Label::primary(span, format!("found '{}'", found))
+        };
+        Some(Self::error(err.to_string(), span).with_label(label))
+    }
+
+    /// Adds a label to the diagnostic.
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Adds a note, which is shown below the source snippet.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Gets the severity of the diagnostic.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Gets the main message of the diagnostic.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the primary span of the diagnostic.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the labels of the diagnostic.
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Gets the notes of the diagnostic.
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Renders the diagnostic against the source file it was created from.
+    ///
+    /// The output has the same layout as the diagnostics of `rustc`:
+    /// a header, the location as `name:line:column`, the lines of source
+    /// that are labelled with the labelled regions underlined, and the notes.
+    /// Lines and columns are shown one indexed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ Diagnostic, Label, LineCol, Span };
+    ///
+    /// let span = Span::new(LineCol::new(0, 4), LineCol::new(0, 9));
+    /// let diag = Diagnostic::error("Expected 'there'", span)
+    ///     .with_label(Label::primary(span, "found 'hello'"));
+    /// assert_eq!(
+    ///     diag.display("hi.mcfunction", "say hello\n").to_string(),
+    ///     "\
+    /// error: Expected 'there'
+    ///  --> hi.mcfunction:1:5
+    ///   |
+    /// 1 | say hello
+    ///   |     ^^^^^ found 'hello'
+    /// "
+    /// );
+    /// ```
+    pub fn display<'a>(&'a self, name: &'a str, src: &'a str) -> DiagnosticDisplay<'a> {
+        DiagnosticDisplay {
+            diag: self,
+            name,
+            src,
+        }
+    }
+}
+
+/// Helper struct for rendering a [`Diagnostic`] with `format!` and `{}`.
+///
+/// Created by [`Diagnostic::display`].
+///
+/// [`Diagnostic`]: ./struct.Diagnostic.html
+/// [`Diagnostic::display`]: ./struct.Diagnostic.html#method.display
+pub struct DiagnosticDisplay<'a> {
+    diag: &'a Diagnostic,
+    name: &'a str,
+    src: &'a str,
+}
+
+impl Display for DiagnosticDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let diag = self.diag;
+        let index = LineIndex::new(self.src);
+        let lines: Vec<&str> = (0..index.line_count())
+            .map(|l| &self.src[index.line_range(l).unwrap()])
+            .collect();
+        writeln!(f, "{}: {}", diag.severity, diag.message)?;
+
+        let labels: Vec<Label> = if diag.labels.is_empty() {
+            vec![Label::primary(diag.span, "")]
+        } else {
+            diag.labels.clone()
+        };
+        // The lines that have a part underlined, in order
+        let mut shown: Vec<usize> = labels
+            .iter()
+            .flat_map(|l| covered_lines(l.span, &lines))
+            .collect();
+        shown.sort_unstable();
+        shown.dedup();
+
+        let width = shown
+            .last()
+            .map_or(1, |&l| (l + 1).to_string().len())
+            .max(1);
+        let pad = " ".repeat(width);
+
+        let start = diag.span.start();
+        let col = lines
+            .get(start.line())
+            .map_or(start.col(), |line| display_col(line, start.col()));
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            pad,
+            self.name,
+            start.line() + 1,
+            col + 1
+        )?;
+        writeln!(f, "{} |", pad)?;
+
+        let mut prev = None;
+        for &line in &shown {
+            if matches!(prev, Some(p) if p + 1 < line) {
+                writeln!(f, "...")?;
+            }
+            prev = Some(line);
+            let text = lines[line];
+            writeln!(
+                f,
+                "{:>width$} | {}",
+                line + 1,
+                expand_tabs(text),
+                width = width
+            )?;
+            for label in &labels {
+                let (from, to) = match underline(label.span, line, text) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                let mark = if label.primary { "^" } else { "-" };
+                write!(
+                    f,
+                    "{} | {}{}",
+                    pad,
+                    " ".repeat(from),
+                    mark.repeat((to - from).max(1))
+                )?;
+                if label.span.end().line() == line && !label.message.is_empty() {
+                    write!(f, " {}", label.message)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        if !diag.notes.is_empty() {
+            writeln!(f, "{} |", pad)?;
+            for note in &diag.notes {
+                writeln!(f, "{} = note: {}", pad, note)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Gets the lines that should be shown for a span.
+// A span that ends at the very start of a line does not underline anything on it.
+fn covered_lines(span: Span, lines: &[&str]) -> impl Iterator<Item = usize> {
+    let first = span.start().line();
+    let last = if span.end().col() == 0 && span.end().line() > first {
+        span.end().line() - 1
+    } else {
+        span.end().line()
+    };
+    first..=last.min(lines.len().saturating_sub(1))
+}
+
+// Gets the display columns of the part of `line` that is underlined for `span`
+fn underline(span: Span, line: usize, text: &str) -> Option<(usize, usize)> {
+    let (start, end) = (span.start(), span.end());
+    if line < start.line() || line > end.line() {
+        return None;
+    }
+    if line == end.line() && end.col() == 0 && line > start.line() {
+        return None;
+    }
+    let from = if line == start.line() {
+        display_col(text, start.col())
+    } else {
+        0
+    };
+    let to = if line == end.line() {
+        display_col(text, end.col())
+    } else {
+        display_col(text, text.len())
+    };
+    Some((from, to.max(from)))
+}
+
+const TAB_WIDTH: usize = 4;
+
+// Converts a byte column into the column it is shown at
+fn display_col(text: &str, col: usize) -> usize {
+    let mut out = 0;
+    for (i, c) in text.char_indices() {
+        if i >= col {
+            break;
+        }
+        out += if c == '\t' { TAB_WIDTH } else { 1 };
+    }
+    out + col.saturating_sub(text.len())
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
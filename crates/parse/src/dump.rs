@@ -0,0 +1,177 @@
+use crate::{
+    ast::{Ast, AstView, SyntaxKind, WalkEvent},
+    parser::Language,
+    syntax::GroupType,
+};
+
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::fmt::{self, Display, Formatter};
+
+/// Serializes the tree as its root node and its errors.
+///
+/// ```json
+/// { "root": <node>, "errors": [{ "message": "Expected ']'", "span": <span> }] }
+/// ```
+///
+/// See the implementation for [`AstView`] for the format of a node.
+///
+/// [`AstView`]: ./struct.AstView.html
+impl<T: AsRef<str>, L: Language> Serialize for Ast<T, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("root", &self.root())?;
+        map.serialize_entry("errors", &Errors(self))?;
+        map.end()
+    }
+}
+
+struct Errors<'a, T: AsRef<str>, L: Language>(&'a Ast<T, L>);
+
+impl<T: AsRef<str>, L: Language> Serialize for Errors<'_, T, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for err in self.0.errors() {
+            seq.serialize_element(&ErrorEntry(err))?;
+        }
+        seq.end()
+    }
+}
+
+struct ErrorEntry<'a, T: AsRef<str>, L: Language>(AstView<'a, T, L>);
+
+impl<T: AsRef<str>, L: Language> Serialize for ErrorEntry<'_, T, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        if let SyntaxKind::Error(err) = self.0.kind() {
+            map.serialize_entry("message", &err.to_string())?;
+        }
+        map.serialize_entry("span", &self.0.span())?;
+        map.end()
+    }
+}
+
+/// Serializes the node and all of its children.
+///
+/// Every node is an object with these fields:
+///
+/// - `kind`: one of `"root"`, `"group"`, `"joined"`, `"token"` or `"error"`.
+/// - `type`: the [`GroupType::name`] of the group, or the `Debug` name of the token type.
+///   Not present on errors.
+/// - `message`: the error message. Only present on errors.
+/// - `span`: `{ "start": <pos>, "end": <pos> }`, where a position is
+///   `{ "line": 0, "col": 0 }`. Lines and columns are zero indexed,
+///   and columns are counted in bytes.
+/// - `range`: the byte offsets of the start and end of the node, as `[start, end]`.
+/// - `text`: the source text. Only present on tokens.
+/// - `children`: the child nodes, in order. Not present on tokens and errors.
+///
+/// Joining the `text` of every token in order gives back the source.
+///
+/// [`GroupType::name`]: ./trait.GroupType.html#method.name
+impl<T: AsRef<str>, L: Language> Serialize for AstView<'_, T, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let range = self.range();
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", kind_name(self.kind()))?;
+        match self.kind() {
+            SyntaxKind::Root(g) | SyntaxKind::Group(g) | SyntaxKind::Joined(g) => {
+                map.serialize_entry("type", &g.name())?
+            }
+            SyntaxKind::Token(tk) => map.serialize_entry("type", &format!("{:?}", tk))?,
+            SyntaxKind::Error(err) => map.serialize_entry("message", &err.to_string())?,
+        }
+        map.serialize_entry("span", &self.span())?;
+        map.serialize_entry("range", &[range.start, range.end])?;
+        match self.kind() {
+            SyntaxKind::Token(_) => map.serialize_entry("text", self.string())?,
+            SyntaxKind::Error(_) => {}
+            _ => map.serialize_entry("children", &Children(*self))?,
+        }
+        map.end()
+    }
+}
+
+struct Children<'a, T: AsRef<str>, L: Language>(AstView<'a, T, L>);
+
+impl<T: AsRef<str>, L: Language> Serialize for Children<'_, T, L> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.children())
+    }
+}
+
+fn kind_name<L: Language>(kind: &SyntaxKind<L>) -> &'static str {
+    match kind {
+        SyntaxKind::Root(_) => "root",
+        SyntaxKind::Group(_) => "group",
+        SyntaxKind::Joined(_) => "joined",
+        SyntaxKind::Token(_) => "token",
+        SyntaxKind::Error(_) => "error",
+    }
+}
+
+/// Helper struct for writing a node as an indented S-expression.
+///
+/// Created by [`AstView::sexp`]. Every node is written on its own line,
+/// indented by two spaces for each level of depth, as
+/// `(<kind> <type> <span>` followed by its children and a closing parenthesis.
+/// Tokens also contain their text, and errors contain their message,
+/// both as quoted strings. A command file containing `say hi` and a line break is written as:
+///
+/// ```text
+/// (root File 0:0 - 1:0
+///   (group Command 0:0 - 0:6
+///     (group CommandNode 0:0 - 0:3
+///       (token Word 0:0 - 0:3 "say"))
+///     (token Whitespace 0:3 - 0:4 " ")
+///     (group CommandNode 0:4 - 0:6
+///       (group UnquotedString 0:4 - 0:6
+///         (token Word 0:4 - 0:6 "hi"))))
+///   (token Eof 0:6 - 1:0 "\n"))
+/// ```
+///
+/// [`AstView::sexp`]: ./ast/struct.AstView.html#method.sexp
+pub struct Sexp<'a, T: AsRef<str>, L: Language>(AstView<'a, T, L>);
+
+impl<'a, T: AsRef<str>, L: Language> AstView<'a, T, L> {
+    /// Gets a value that displays the node as an S-expression, see [`Sexp`].
+    ///
+    /// [`Sexp`]: ../struct.Sexp.html
+    pub fn sexp(self) -> Sexp<'a, T, L> {
+        Sexp(self)
+    }
+}
+
+impl<T: AsRef<str>, L: Language> Display for Sexp<'_, T, L> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut depth = 0;
+        let mut first = true;
+        for evt in self.0.walk() {
+            match evt {
+                WalkEvent::Enter(node) => {
+                    if !first {
+                        writeln!(f)?;
+                    }
+                    first = false;
+                    write!(f, "{}({} ", "  ".repeat(depth), kind_name(node.kind()))?;
+                    match node.kind() {
+                        SyntaxKind::Root(g) | SyntaxKind::Group(g) | SyntaxKind::Joined(g) => {
+                            write!(f, "{} {}", g.name(), node.span())?
+                        }
+                        SyntaxKind::Token(tk) => {
+                            write!(f, "{:?} {} {:?}", tk, node.span(), node.string())?
+                        }
+                        SyntaxKind::Error(err) => {
+                            write!(f, "{} {:?}", node.span(), err.to_string())?
+                        }
+                    }
+                    depth += 1;
+                }
+                WalkEvent::Leave(_) => {
+                    depth -= 1;
+                    write!(f, ")")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{LineCol, LineIndex, Span};
+
+/// A single replacement of a region of source text.
+///
+/// The region is described by a [`Span`], so an edit can be
+/// created directly from the positions an editor reports.
+///
+/// [`Span`]: ./struct.Span.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TextEdit {
+    span: Span,
+    text: String,
+}
+
+impl TextEdit {
+    /// Constructs an edit that replaces `span` with `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ LineCol, Span, TextEdit };
+    ///
+    /// let edit = TextEdit::new(Span::new(LineCol::new(0, 4), LineCol::new(0, 9)), "there");
+    /// assert_eq!(edit.apply("say hello"), "say there");
+    /// ```
+    pub fn new(span: Span, text: impl Into<String>) -> Self {
+        TextEdit {
+            span,
+            text: text.into(),
+        }
+    }
+
+    /// Constructs an edit that inserts `text` at `at`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ LineCol, TextEdit };
+    ///
+    /// let edit = TextEdit::insert(LineCol::new(1, 0), "say hi\n");
+    /// assert_eq!(edit.apply("# a\n# b"), "# a\nsay hi\n# b");
+    /// ```
+    pub fn insert(at: LineCol, text: impl Into<String>) -> Self {
+        Self::new(Span::new(at, at), text)
+    }
+
+    /// Constructs an edit that deletes the text in `span`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ LineCol, Span, TextEdit };
+    ///
+    /// let edit = TextEdit::delete(Span::new(LineCol::new(0, 3), LineCol::new(1, 3)));
+    /// assert_eq!(edit.apply("say hi\nsay bye"), "say bye");
+    /// ```
+    pub fn delete(span: Span) -> Self {
+        Self::new(span, String::new())
+    }
+
+    /// Gets the span of the text that is replaced.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the replacement text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Applies this edit to `src`, returning the new text.
+    ///
+    /// Lines are terminated by `\n`, `\r\n` or a lone `\r`,
+    /// the same way the mcfunction lexer splits lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span of the edit is not inside of `src`,
+    /// or if its end comes before its start.
+    pub fn apply(&self, src: &str) -> String {
+        let index = LineIndex::new(src);
+        let start = index
+            .offset(self.span.start())
+            .expect("Edit start is outside of the source");
+        let end = index
+            .offset(self.span.end())
+            .expect("Edit end is outside of the source");
+        assert!(
+            start <= end,
+            "Edit end {} comes before its start {}",
+            self.span.end(),
+            self.span.start()
+        );
+        let mut out = String::with_capacity(src.len() - (end - start) + self.text.len());
+        out.push_str(&src[..start]);
+        out.push_str(&self.text);
+        out.push_str(&src[end..]);
+        out
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{parser::Language, Ast, AstView, LineIndex, Span, SyntaxKind, TextEdit};
+
+/// Collects changes to the nodes of an [`Ast`] and turns them into [`TextEdit`]s.
+///
+/// The tree itself is never changed. Instead, the edits are applied to the
+/// source, which can then be parsed again. Languages with incremental
+/// reparsing can reuse most of the old tree by applying the edits one by one.
+///
+/// # Panics
+///
+/// The methods that add an edit panic if the edit overlaps an earlier one,
+/// if the node is an error, or if the node is from a different tree.
+///
+/// [`Ast`]: ./ast/struct.Ast.html
+/// [`TextEdit`]: ./struct.TextEdit.html
+pub struct AstEditor<'a, T: AsRef<str>, L: Language> {
+    ast: &'a Ast<T, L>,
+    // Byte ranges and their replacements, sorted by range
+    edits: Vec<(usize, usize, String)>,
+}
+
+impl<'a, T: AsRef<str>, L: Language> AstEditor<'a, T, L> {
+    /// Constructs an editor without any edits.
+    pub fn new(ast: &'a Ast<T, L>) -> Self {
+        AstEditor { ast, edits: vec![] }
+    }
+
+    /// Replaces the text of `node` with `text`.
+    pub fn replace(&mut self, node: AstView<'a, T, L>, text: impl Into<String>) {
+        let range = self.range(node);
+        self.push(range.start, range.end, text.into());
+    }
+
+    /// Removes `node` from the source.
+    pub fn delete(&mut self, node: AstView<'a, T, L>) {
+        self.replace(node, String::new());
+    }
+
+    /// Inserts `text` directly before `node`.
+    ///
+    /// Several insertions at the same place are kept in the order they were made.
+    pub fn insert_before(&mut self, node: AstView<'a, T, L>, text: impl Into<String>) {
+        let start = self.range(node).start;
+        self.push(start, start, text.into());
+    }
+
+    /// Inserts `text` directly after `node`.
+    ///
+    /// Several insertions at the same place are kept in the order they were made.
+    pub fn insert_after(&mut self, node: AstView<'a, T, L>, text: impl Into<String>) {
+        let end = self.range(node).end;
+        self.push(end, end, text.into());
+    }
+
+    /// Gets the smallest set of text edits that make the changes.
+    ///
+    /// Edits that touch each other are joined, and text that a replacement
+    /// leaves the same is not part of an edit.
+    /// The edits are returned from the end of the source to the start,
+    /// so applying them one after another to the old source is the same
+    /// as applying all of them at once.
+    pub fn text_edits(&self) -> Vec<TextEdit> {
+        let src = self.ast.src();
+        let index = LineIndex::new(src);
+        let mut merged: Vec<(usize, usize, String)> = vec![];
+        for (start, end, text) in &self.edits {
+            match merged.last_mut() {
+                Some((_, last_end, last_text)) if *last_end == *start => {
+                    *last_end = *end;
+                    last_text.push_str(text);
+                }
+                _ => merged.push((*start, *end, text.clone())),
+            }
+        }
+        merged
+            .into_iter()
+            .rev()
+            .filter_map(|(start, end, text)| {
+                let old = &src[start..end];
+                let prefix = common_prefix(old, &text);
+                let suffix = common_suffix(&old[prefix..], &text[prefix..]);
+                if prefix + suffix == old.len() && old.len() == text.len() {
+                    return None;
+                }
+                let span = Span::new(
+                    index.line_col(start + prefix).unwrap(),
+                    index.line_col(end - suffix).unwrap(),
+                );
+                Some(TextEdit::new(span, &text[prefix..text.len() - suffix]))
+            })
+            .collect()
+    }
+
+    /// Gets the source with all of the changes made.
+    pub fn apply(&self) -> String {
+        let src = self.ast.src();
+        let mut out = String::with_capacity(src.len());
+        let mut last = 0;
+        for (start, end, text) in &self.edits {
+            out.push_str(&src[last..*start]);
+            out.push_str(text);
+            last = *end;
+        }
+        out.push_str(&src[last..]);
+        out
+    }
+
+    fn range(&self, node: AstView<'a, T, L>) -> std::ops::Range<usize> {
+        assert!(
+            std::ptr::eq(node.ast(), self.ast),
+            "Node is from a different tree"
+        );
+        assert!(
+            !matches!(node.kind(), SyntaxKind::Error(_)),
+            "Error nodes have no text to edit"
+        );
+        node.range()
+    }
+
+    fn push(&mut self, start: usize, end: usize, text: String) {
+        // Insertions come before replacements that start at the same place
+        let ind = self
+            .edits
+            .iter()
+            .position(|(s, e, _)| (*s, *e) > (start, end))
+            .unwrap_or(self.edits.len());
+        // Edits that only touch at one end do not overlap,
+        // but an insertion strictly inside of a replacement does
+        assert!(
+            !self.edits.iter().any(|(s, e, _)| *s < end && start < *e),
+            "Edits must not overlap"
+        );
+        self.edits.insert(ind, (start, end, text));
+    }
+}
+
+// Length in bytes of the longest common prefix, on a character boundary
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| x != y)
+        .map_or_else(|| a.len().min(b.len()), |((i, _), _)| i)
+}
+
+// Length in bytes of the longest common suffix, on a character boundary
+fn common_suffix(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .rev()
+        .zip(b.chars().rev())
+        .find(|((_, x), y)| x != y)
+        .map_or_else(
+            || a.len().min(b.len()),
+            |((i, x), _)| a.len() - i - x.len_utf8(),
+        )
+}
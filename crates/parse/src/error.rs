@@ -1,4 +1,7 @@
-use crate::{parser::Language, syntax::TokenKind};
+use crate::{
+    parser::Language,
+    syntax::{GroupType, TokenKind},
+};
 
 use std::borrow::Cow;
 use std::fmt;
@@ -18,15 +21,7 @@ impl<T: TokenKind> ExpectedToken<T> {
 
 impl<T: TokenKind> fmt::Display for ExpectedToken<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Expected one of ")?;
-        for (i, x) in self.expected().iter().enumerate() {
-            if i == 0 {
-                write!(f, "'{}'", x)?;
-            } else {
-                write!(f, ", '{}'", x)?;
-            }
-        }
-        Ok(())
+        write_expected(f, self.expected())
     }
 }
 
@@ -49,16 +44,24 @@ impl<L: Language> ExpectedLit<L> {
 
 impl<L: Language> fmt::Display for ExpectedLit<L> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Expected one of ")?;
-        for (i, (x, _)) in self.expected().iter().enumerate() {
-            if i == 0 {
-                write!(f, "'{}'", x)?;
-            } else {
-                write!(f, ", '{}'", x)?;
-            }
+        let lits: Vec<_> = self.expected().iter().map(|(x, _)| x).collect();
+        write_expected(f, &lits)
+    }
+}
+
+// Writes a list such as "Expected 'a', 'b' or 'c'"
+fn write_expected<T: fmt::Display>(f: &mut fmt::Formatter, expected: &[T]) -> fmt::Result {
+    write!(f, "Expected ")?;
+    for (i, x) in expected.iter().enumerate() {
+        if i == 0 {
+            write!(f, "'{}'", x)?;
+        } else if i + 1 == expected.len() {
+            write!(f, " or '{}'", x)?;
+        } else {
+            write!(f, ", '{}'", x)?;
         }
-        Ok(())
     }
+    Ok(())
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -66,6 +69,9 @@ pub enum ParseError<L: Language> {
     Token(ExpectedToken<L::TokenKind>),
     Lit(ExpectedLit<L>),
     Group(L::GroupType),
+    /// A token that the grammar did not know what to do with,
+    /// which the parser skipped so that it would not get stuck.
+    Unexpected,
 }
 
 impl<L: Language> fmt::Display for ParseError<L> {
@@ -75,8 +81,14 @@ impl<L: Language> fmt::Display for ParseError<L> {
         match self {
             Token(t) => write!(f, "{}", t),
             Lit(l) => write!(f, "{}", l),
-            // TODO: Add better error message for group error
-            Group(g) => write!(f, "Error while parsing '{:?}'", g),
+            Group(g) => {
+                write!(f, "Expected {}", g.description())?;
+                if let Some(example) = g.example() {
+                    write!(f, " such as `{}`", example)?;
+                }
+                Ok(())
+            }
+            Unexpected => write!(f, "Unexpected token"),
         }
     }
 }
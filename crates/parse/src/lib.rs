@@ -1,12 +1,25 @@
 #![deny(unsafe_code)]
 pub mod ast;
+pub mod cst;
 pub mod error;
 pub mod parser;
 
+mod diagnostic;
+mod dump;
+mod edit;
+mod editor;
+mod line_index;
 mod span;
 mod syntax;
+mod trace;
 
 pub use ast::{Ast, AstView, SyntaxKind};
+pub use diagnostic::{Diagnostic, DiagnosticDisplay, Label, Severity};
+pub use dump::Sexp;
+pub use edit::TextEdit;
+pub use editor::AstEditor;
 pub use error::ParseError;
+pub use line_index::LineIndex;
 pub use span::{LineCol, Span};
-pub use syntax::{Token, TokenKind, TokenSet};
+pub use syntax::{GroupType, Token, TokenKind, TokenSet};
+pub use trace::{Trace, TraceEvent, TracedToken};
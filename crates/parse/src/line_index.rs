@@ -0,0 +1,239 @@
+use crate::LineCol;
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Converts between byte offsets, [`LineCol`]s and UTF-16 positions in a source text.
+///
+/// The columns of a [`LineCol`] are byte offsets from the start of the line,
+/// which is what the lexers produce. Editors that speak the language server
+/// protocol count columns in UTF-16 code units instead, which is different
+/// for any character outside of ASCII.
+///
+/// Lines are terminated by `\n`, `\r\n` or a lone `\r`,
+/// the same way the mcfunction lexer splits lines.
+///
+/// [`LineCol`]: ./struct.LineCol.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineIndex {
+    // Byte offset of the start of every line
+    starts: Vec<usize>,
+    // Byte offset of the end of every line, before the line break
+    ends: Vec<usize>,
+    // The characters of each line that are longer than one byte, in order
+    wide: HashMap<usize, Vec<WideChar>>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+struct WideChar {
+    // Byte column of the character
+    col: usize,
+    utf8_len: usize,
+    utf16_len: usize,
+}
+
+impl LineIndex {
+    /// Constructs the index of `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::LineIndex;
+    ///
+    /// let index = LineIndex::new("say a\r\nsay b\rsay c\n");
+    /// assert_eq!(index.line_count(), 4);
+    /// ```
+    pub fn new(src: &str) -> Self {
+        let mut starts = vec![0];
+        let mut ends = vec![];
+        let mut wide: HashMap<usize, Vec<WideChar>> = HashMap::new();
+        let mut chars = src.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\r' | '\n' => {
+                    ends.push(i);
+                    let mut next = i + 1;
+                    if c == '\r' {
+                        if let Some((_, '\n')) = chars.peek() {
+                            chars.next();
+                            next += 1;
+                        }
+                    }
+                    starts.push(next);
+                }
+                _ if c.len_utf8() > 1 => {
+                    let line = starts.len() - 1;
+                    wide.entry(line).or_default().push(WideChar {
+                        col: i - starts[line],
+                        utf8_len: c.len_utf8(),
+                        utf16_len: c.len_utf16(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        ends.push(src.len());
+        LineIndex { starts, ends, wide }
+    }
+
+    /// Gets the number of lines.
+    ///
+    /// A text that ends with a line break has an empty last line,
+    /// so the number of lines is always at least one.
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Gets the byte offset of the start of `line`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::LineIndex;
+    ///
+    /// let index = LineIndex::new("say a\r\nsay b");
+    /// assert_eq!(index.line_start(1), Some(7));
+    /// assert_eq!(index.line_start(2), None);
+    /// ```
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.starts.get(line).copied()
+    }
+
+    /// Gets the byte range of `line`, without its line break.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::LineIndex;
+    ///
+    /// let src = "say a\r\nsay b";
+    /// let index = LineIndex::new(src);
+    /// assert_eq!(&src[index.line_range(0).unwrap()], "say a");
+    /// ```
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        Some(self.starts.get(line).copied()?..self.ends[line])
+    }
+
+    /// Converts a position to a byte offset.
+    ///
+    /// Returns `None` if the position is not inside of the text,
+    /// is past the end of its line or is not on a character boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ LineCol, LineIndex };
+    ///
+    /// let index = LineIndex::new("say é\nsay b");
+    /// assert_eq!(index.offset(LineCol::new(1, 2)), Some(9));
+    /// assert_eq!(index.offset(LineCol::new(0, 5)), None);
+    /// assert_eq!(index.offset(LineCol::new(0, 7)), None);
+    /// ```
+    pub fn offset(&self, pos: LineCol) -> Option<usize> {
+        let range = self.line_range(pos.line())?;
+        if pos.col() > range.end - range.start || self.inside_char(pos) {
+            None
+        } else {
+            Some(range.start + pos.col())
+        }
+    }
+
+    /// Converts a byte offset to a position.
+    ///
+    /// Returns `None` if the offset is past the end of the text,
+    /// is inside of a `\r\n` line break or is not on a character boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ LineCol, LineIndex };
+    ///
+    /// let index = LineIndex::new("say a\r\nsay b");
+    /// assert_eq!(index.line_col(5), Some(LineCol::new(0, 5)));
+    /// assert_eq!(index.line_col(6), None);
+    /// assert_eq!(index.line_col(7), Some(LineCol::new(1, 0)));
+    /// ```
+    pub fn line_col(&self, offset: usize) -> Option<LineCol> {
+        let line = self.starts.partition_point(|&s| s <= offset) - 1;
+        let range = self.line_range(line)?;
+        if offset > range.end {
+            // Either past the end of the text or inside of a line break
+            return None;
+        }
+        let pos = LineCol::new(line, offset - range.start);
+        if self.inside_char(pos) {
+            None
+        } else {
+            Some(pos)
+        }
+    }
+
+    /// Converts a position with a byte column to one with a UTF-16 column.
+    ///
+    /// Returns `None` in the same cases as [`offset`].
+    ///
+    /// [`offset`]: #method.offset
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ LineCol, LineIndex };
+    ///
+    /// // 'é' is two bytes but one UTF-16 code unit, '𝄞' is four bytes and two code units
+    /// let index = LineIndex::new("say é𝄞!");
+    /// assert_eq!(index.to_utf16(LineCol::new(0, 10)), Some(LineCol::new(0, 7)));
+    /// ```
+    pub fn to_utf16(&self, pos: LineCol) -> Option<LineCol> {
+        self.offset(pos)?;
+        let mut col = pos.col();
+        for c in self.wide_chars(pos.line()) {
+            if c.col >= pos.col() {
+                break;
+            }
+            col -= c.utf8_len - c.utf16_len;
+        }
+        Some(LineCol::new(pos.line(), col))
+    }
+
+    /// Converts a position with a UTF-16 column to one with a byte column.
+    ///
+    /// Returns `None` if the position is not inside of the text,
+    /// is past the end of its line or is between the two halves of a surrogate pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcfunction_parse::{ LineCol, LineIndex };
+    ///
+    /// let index = LineIndex::new("say é𝄞!");
+    /// assert_eq!(index.from_utf16(LineCol::new(0, 7)), Some(LineCol::new(0, 10)));
+    /// assert_eq!(index.from_utf16(LineCol::new(0, 6)), None);
+    /// ```
+    pub fn from_utf16(&self, pos: LineCol) -> Option<LineCol> {
+        let mut col = pos.col();
+        for c in self.wide_chars(pos.line()) {
+            // The UTF-16 column of the character
+            let start = c.col - (col - pos.col());
+            if start >= pos.col() {
+                break;
+            }
+            if pos.col() < start + c.utf16_len {
+                return None;
+            }
+            col += c.utf8_len - c.utf16_len;
+        }
+        let pos = LineCol::new(pos.line(), col);
+        self.offset(pos).map(|_| pos)
+    }
+
+    fn wide_chars(&self, line: usize) -> &[WideChar] {
+        self.wide.get(&line).map_or(&[], |v| v.as_slice())
+    }
+
+    // Whether the position is after the first byte of a character that is longer than one byte
+    fn inside_char(&self, pos: LineCol) -> bool {
+        self.wide_chars(pos.line())
+            .iter()
+            .any(|c| c.col < pos.col() && pos.col() < c.col + c.utf8_len)
+    }
+}
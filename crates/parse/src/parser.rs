@@ -1,11 +1,16 @@
 use crate::{
     error::{ExpectedLit, ExpectedToken},
-    syntax::TokenKind,
-    tokenset, Ast, LineCol, ParseError, Token, TokenSet,
+    syntax::{GroupType, TokenKind},
+    tokenset, Ast, LineCol, ParseError, Token, TokenSet, Trace, TraceEvent, TracedToken,
 };
 
+use std::cell::{Cell, RefCell};
+use std::panic::Location;
 use util::DropBomb;
 
+// How many times the parser may look at the next token without consuming anything
+const FUEL: u32 = 256;
+
 #[derive(Debug)]
 pub struct Parser<'t, 's, L: Language> {
     pub(crate) tokens: &'t [Token<L::TokenKind>],
@@ -13,11 +18,22 @@ pub struct Parser<'t, 's, L: Language> {
     src: &'s str,
     skip_ws: bool,
     root: L::GroupType,
+    // Lookups left until the parser is considered stuck, reset whenever a token is consumed
+    fuel: Cell<u32>,
+    // Set once the fuel has run out in a lenient parser, makes the parser act like it is at the EOF
+    // until the next line, which ends any loop that stops at the EOF
+    stuck: Cell<bool>,
+    // Whether a stuck parser panics instead of recovering, on by default in debug builds
+    strict: bool,
+    // The events recorded so far if tracing is enabled
+    trace: RefCell<Option<Vec<TraceEvent<L>>>>,
+    // The tokens that the enclosing rules can continue from after an error
+    recovery: Vec<TokenSet<L::TokenKind>>,
 }
 
 pub trait Language: 'static + Copy + Clone + PartialEq + Eq + std::hash::Hash {
     type TokenKind: TokenKind;
-    type GroupType: std::fmt::Debug + Copy + Eq;
+    type GroupType: GroupType;
 
     const ERROR_GROUP: Self::GroupType;
 }
@@ -35,9 +51,15 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
             src,
             skip_ws,
             root,
+            fuel: Cell::new(FUEL),
+            stuck: Cell::new(false),
+            strict: cfg!(debug_assertions),
+            trace: RefCell::new(None),
+            recovery: Vec::new(),
         }
     }
 
+    #[track_caller]
     pub fn start(&mut self, kind: L::GroupType, skip: StartInfo) -> Marker<'t, L> {
         if self.skip_ws {
             self.skip_ws = false;
@@ -47,6 +69,7 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         self.start_no_skip(kind, skip)
     }
 
+    #[track_caller]
     pub fn start_no_skip(&mut self, kind: L::GroupType, skip: StartInfo) -> Marker<'t, L> {
         let mk = Marker(
             self.tokens,
@@ -55,6 +78,7 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
             DropBomb::new("Markers should either be finished or cancelled"),
         );
         self.skip_ws = skip == StartInfo::Skip;
+        self.trace_event(|token| TraceEvent::Start { kind, token });
         self.push_event(Event::Start {
             kind,
             join: skip == StartInfo::Join,
@@ -63,6 +87,7 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
     }
 
     pub fn finish(&mut self, mut marker: Marker<'t, L>) {
+        self.trace_event(|token| TraceEvent::Finish { token });
         self.push_event(Event::End {
             linecol: self.tokens[0].span().start(),
             off: self.tokens[0].start(),
@@ -72,6 +97,7 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
     }
 
     pub fn cancel(&mut self, mut marker: Marker<'t, L>) {
+        self.trace_event(|token| TraceEvent::Cancel { token });
         self.events.truncate(marker.1);
         self.tokens = marker.0;
         self.skip_ws = marker.2;
@@ -88,24 +114,33 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn nth(&self, off: usize) -> L::TokenKind {
         // If accessing past the end of the file, just return the EOF
         self.nth_tk(off).kind()
     }
 
+    #[track_caller]
     pub(crate) fn nth_tk(&self, n: usize) -> Token<L::TokenKind> {
         assert!(n <= 1);
+        // Looking past the EOF gives the EOF again
+        let last = self.tokens.len() - 1;
+        if self.stuck.get() || !self.burn_fuel() {
+            return self.tokens[last];
+        }
         if self.skip_ws {
             let mut off = 0;
-            for _ in 0..=n {
-                while tokenset!(self.tokens[off].kind() => L::TokenKind::WHITESPACE) {
+            for i in 0..=n {
+                if i > 0 {
+                    off = (off + 1).min(last);
+                }
+                while off < last && tokenset!(self.tokens[off].kind() => L::TokenKind::WHITESPACE) {
                     off += 1;
                 }
-                off += 1;
             }
-            self.tokens[off - 1]
+            self.tokens[off]
         } else {
-            self.tokens[n]
+            self.tokens[n.min(last)]
         }
     }
 
@@ -114,14 +149,74 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         self.tokens[n].kind()
     }
 
+    /// Sets whether the parser panics when it gets stuck, see [`progressed`].
+    ///
+    /// Strict mode is on by default in debug builds.
+    ///
+    /// [`progressed`]: #method.progressed
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Restores the lookup budget that is otherwise only restored by consuming a token.
+    ///
+    /// Rules that try an open-ended number of alternatives at the same token
+    /// should call this before every alternative.
+    pub fn refuel(&self) {
+        self.fuel.set(FUEL);
+    }
+
+    // Uses up one lookup, returns `false` if there was none left in a lenient parser
+    #[track_caller]
+    fn burn_fuel(&self) -> bool {
+        let fuel = self.fuel.get();
+        if fuel == 0 {
+            if self.strict {
+                panic!(
+                    "Parser is stuck at {} without consuming a token",
+                    Location::caller()
+                );
+            }
+            self.stuck.set(true);
+            false
+        } else {
+            self.fuel.set(fuel - 1);
+            true
+        }
+    }
+
+    #[track_caller]
     pub fn at(&self, kind: L::TokenKind) -> bool {
-        self.nth(0) == kind
+        let matched = self.is_at(kind);
+        self.trace_lookahead(|| format!("'{}'", kind), matched);
+        matched
     }
 
+    #[track_caller]
     pub fn not_at(&self, kind: L::TokenKind) -> bool {
-        !(self.at(L::TokenKind::EOF) || self.at(kind))
+        !(self.is_at(L::TokenKind::EOF) || self.at(kind))
     }
 
+    // The checks that the parser makes for itself, which are left out of the trace
+    #[track_caller]
+    fn is_at(&self, kind: L::TokenKind) -> bool {
+        self.nth(0) == kind
+    }
+
+    #[track_caller]
+    fn is_at_tokens(&self, set: TokenSet<L::TokenKind>) -> bool {
+        tokenset!(self.nth_tk(0).kind() => set)
+    }
+
+    #[track_caller]
+    fn is_at_keyword(&self, ex: &[(&str, L::GroupType)]) -> bool {
+        self.is_at(L::TokenKind::WORD) && {
+            let tk = self.nth_tk(0).string(self.src);
+            ex.iter().any(|(x, _)| tk == *x)
+        }
+    }
+
+    #[track_caller]
     pub fn bump(&mut self) {
         if self.skip_ws {
             self.skip_ws = false;
@@ -130,20 +225,34 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
         // Never progress past the EOF so there will always be one token in the slice
         // Don't progress over line breaks either so the parser wont crash and burn
-        if !self.at(L::TokenKind::EOF) {
+        if !self.is_at(L::TokenKind::EOF) {
+            // Any whitespace has been skipped already, so this is the next token
+            self.trace_event(|token| TraceEvent::Bump { token });
             self.push_event(Event::Token(self.nth_tk(0)));
-            self.skip();
+            self.advance();
         }
     }
 
+    /// Moves past the next raw token without any whitespace handling,
+    /// the token is still kept in the tree so no source text is lost
+    #[track_caller]
     pub fn skip(&mut self) {
-        if !self.at(L::TokenKind::EOF) {
+        if !self.is_at(L::TokenKind::EOF) {
+            self.push_event(Event::Token(self.tokens[0]));
+            self.advance();
+        }
+    }
+
+    fn advance(&mut self) {
+        if !self.is_at(L::TokenKind::EOF) {
             self.tokens = &self.tokens[1..];
+            self.fuel.set(FUEL);
         }
     }
 
+    #[track_caller]
     pub fn eat(&mut self, kind: L::TokenKind) -> bool {
-        if self.at(kind) {
+        if self.is_at(kind) {
             self.bump();
             true
         } else {
@@ -151,8 +260,9 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn eat_tokens(&mut self, set: TokenSet<L::TokenKind>) -> bool {
-        if self.at_tokens(set) {
+        if self.is_at_tokens(set) {
             self.bump();
             true
         } else {
@@ -160,10 +270,10 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn expect(&mut self, kind: L::TokenKind) -> bool {
         if !self.eat(kind) {
-            self.events
-                .push(Event::Error(ExpectedToken::new(vec![kind]).into()));
+            self.push_event(Event::Error(ExpectedToken::new(vec![kind]).into()));
             false
         } else {
             true
@@ -171,8 +281,9 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
     }
 
     // Needed for context aware keywords
+    #[track_caller]
     pub fn expect_keyword(&mut self, ex: &'static [(&'static str, L::GroupType)]) -> bool {
-        if self.at(L::TokenKind::WORD) {
+        if self.is_at(L::TokenKind::WORD) {
             let tk = self.nth_tk(0).string(self.src);
             for (x, g) in ex {
                 if tk == *x {
@@ -182,8 +293,7 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
                     return true;
                 }
             }
-            self.events
-                .push(Event::Error(ExpectedLit::from_slice(ex).into()));
+            self.push_event(Event::Error(ExpectedLit::from_slice(ex).into()));
             false
         } else {
             self.expect(L::TokenKind::WORD);
@@ -213,6 +323,11 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
             src: self.src,
             skip_ws: self.skip_ws,
             root: self.root,
+            fuel: Cell::new(FUEL),
+            stuck: Cell::new(false),
+            strict: self.strict,
+            trace: RefCell::new(None),
+            recovery: Vec::new(),
         };
         let mk = parser.start(L::ERROR_GROUP, StartInfo::Join);
         if f(&mut TokenParser(&mut parser)).is_some() {
@@ -224,22 +339,16 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn at_keyword(&self, ex: &[(&str, L::GroupType)]) -> bool {
-        if self.at(L::TokenKind::WORD) {
-            let tk = self.nth_tk(0).string(self.src);
-            for (x, _) in ex {
-                if tk == *x {
-                    return true;
-                }
-            }
-            false
-        } else {
-            false
-        }
+        let matched = self.is_at_keyword(ex);
+        self.trace_lookahead(|| keywords(ex), matched);
+        matched
     }
 
+    #[track_caller]
     pub fn eat_keyword(&mut self, ex: &'static [(&'static str, L::GroupType)]) -> bool {
-        if self.at(L::TokenKind::WORD) {
+        if self.is_at(L::TokenKind::WORD) {
             let tk = self.nth_tk(0).string(self.src);
             for (x, g) in ex {
                 if tk == *x {
@@ -255,17 +364,22 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn at_tokens(&self, set: TokenSet<L::TokenKind>) -> bool {
-        tokenset!(self.nth_tk(0).kind() => set)
+        let matched = self.is_at_tokens(set);
+        self.trace_lookahead(|| format!("{:?}", set), matched);
+        matched
     }
 
+    #[track_caller]
     pub fn err_recover(&mut self, group: L::GroupType, set: TokenSet<L::TokenKind>) {
         self.error(group);
         self.bump_recover(set);
     }
 
+    #[track_caller]
     pub fn bump_recover(&mut self, set: TokenSet<L::TokenKind>) -> bool {
-        if !self.at_tokens(L::TokenKind::DELIMITERS.union(set)) {
+        if !self.is_at_tokens(L::TokenKind::DELIMITERS.union(set)) {
             self.bump();
             false
         } else {
@@ -273,6 +387,84 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
     }
 
+    /// Adds `set` to the tokens that [`recover`] stops at, until [`pop_recovery`] is called.
+    ///
+    /// A rule should add the tokens that it can continue from after an error,
+    /// such as the separator and the closing delimiter of a list,
+    /// so that the rules it calls do not skip past them when they recover.
+    ///
+    /// [`recover`]: #method.recover
+    /// [`pop_recovery`]: #method.pop_recovery
+    pub fn push_recovery(&mut self, set: TokenSet<L::TokenKind>) {
+        self.recovery.push(set);
+    }
+
+    /// Removes the tokens that were added last with [`push_recovery`].
+    ///
+    /// [`push_recovery`]: #method.push_recovery
+    pub fn pop_recovery(&mut self) {
+        self.recovery
+            .pop()
+            .expect("There are no recovery tokens to remove");
+    }
+
+    /// Skips tokens until the parser is at a token in `set`,
+    /// at a token added with [`push_recovery`] or at the EOF.
+    ///
+    /// The skipped tokens are put in an error group. Returns whether any token was skipped.
+    /// Whitespace is only a token to stop at if whitespace is not being skipped.
+    ///
+    /// [`push_recovery`]: #method.push_recovery
+    #[track_caller]
+    pub fn recover(&mut self, set: TokenSet<L::TokenKind>) -> bool {
+        let set = self.recovery.iter().fold(set, |acc, s| acc.union(*s));
+        if self.is_at(L::TokenKind::EOF) || self.is_at_tokens(set) {
+            return false;
+        }
+        // Keep skipping whitespace inside of the error group if it is skipped now
+        let info = if self.skip_ws {
+            StartInfo::Skip
+        } else {
+            StartInfo::None
+        };
+        let mk = self.start(L::ERROR_GROUP, info);
+        while !self.is_at(L::TokenKind::EOF) && !self.is_at_tokens(set) {
+            self.bump();
+        }
+        self.finish(mk);
+        true
+    }
+
+    /// Checks that the parser has consumed a token since the last time it was called with `progress`.
+    ///
+    /// Loops in the grammar should call this once every iteration,
+    /// since a loop that does not consume anything will never end.
+    /// A strict parser panics with the location of the loop when that happens.
+    /// Otherwise the current token is bumped instead and an error is recorded for it,
+    /// or `false` is returned if there is nothing left to bump so that the loop can stop.
+    #[track_caller]
+    pub fn progressed(&mut self, progress: &mut Progress) -> bool {
+        let pos = self.tokens[0].start();
+        if progress.0 != Some(pos) {
+            progress.0 = Some(pos);
+            return true;
+        }
+        if self.strict {
+            panic!(
+                "Parser did not make progress in the loop at {}",
+                Location::caller()
+            );
+        }
+        if self.is_at(L::TokenKind::EOF) {
+            false
+        } else {
+            self.push_event(Event::Error(ParseError::Unexpected));
+            self.bump();
+            progress.0 = Some(self.tokens[0].start());
+            true
+        }
+    }
+
     pub fn lookahead(&mut self) -> Lookahead<'_, 't, 's, L> {
         Lookahead {
             parser: self,
@@ -282,6 +474,21 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
         }
     }
 
+    /// Whether an error has been recorded since the last token was consumed.
+    ///
+    /// A rule that recovers after calling another rule can use this to avoid
+    /// reporting a second error for the same token.
+    pub fn errored(&self) -> bool {
+        for evt in self.events.iter().rev() {
+            match evt {
+                Event::Error(_) => return true,
+                Event::Token(_) => return false,
+                Event::Start { .. } | Event::End { .. } => {}
+            }
+        }
+        false
+    }
+
     pub fn error(&mut self, err: L::GroupType) {
         self.push_event(Event::Error(ParseError::Group(err)))
     }
@@ -293,20 +500,93 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
     }
 
     pub fn change_tokens(&mut self, tks: &'t [Token<L::TokenKind>]) {
+        let last = self.tokens[self.tokens.len() - 1];
         assert!(
-            self.tokens[0].end() == tks[0].start(),
+            last.end() == tks[0].start(),
             "Token streams must be consecutive: {}, {}",
-            self.tokens[0].end(),
+            last.end(),
             tks[0].start()
         );
+        self.flush_tokens();
         self.tokens = tks;
+        self.fuel.set(FUEL);
+    }
+
+    // Attaches every token that hasn't been consumed yet to the current group,
+    // so that the tree always contains all of the source text
+    fn flush_tokens(&mut self) {
+        if self.stuck.replace(false) {
+            self.push_event(Event::Error(ParseError::Unexpected));
+        }
+        for tk in self.tokens {
+            if tk.start() != tk.end() {
+                self.events.push(Event::Token(*tk));
+            }
+        }
+        self.tokens = &self.tokens[self.tokens.len() - 1..];
     }
 
     fn push_event(&mut self, evt: Event<L>) {
+        if let Event::Error(error) = &evt {
+            let error = error.clone();
+            self.trace_event(|token| TraceEvent::Error { error, token });
+        }
         self.events.push(evt);
     }
 
-    pub fn build(self, save_errors: bool) -> Ast<&'s str, L> {
+    /// Starts recording a [`Trace`] of the groups, tokens and errors that the parser adds
+    /// and of the checks that the grammar makes for the next token,
+    /// either directly with [`at`] and friends or through a [`lookahead`].
+    ///
+    /// [`Trace`]: ../struct.Trace.html
+    /// [`at`]: #method.at
+    /// [`lookahead`]: #method.lookahead
+    pub fn enable_trace(&mut self) {
+        let trace = self.trace.get_mut();
+        if trace.is_none() {
+            *trace = Some(Vec::new());
+        }
+    }
+
+    /// Takes the events recorded since tracing was enabled or since the last call.
+    ///
+    /// The trace is empty if tracing is not enabled.
+    pub fn take_trace(&mut self) -> Trace<L> {
+        Trace::new(
+            self.trace
+                .get_mut()
+                .as_mut()
+                .map(std::mem::take)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Adds a message to the trace, which can explain a decision made by the grammar.
+    ///
+    /// `message` is only called if tracing is enabled.
+    pub fn trace(&mut self, message: impl FnOnce() -> String) {
+        if let Some(trace) = self.trace.get_mut() {
+            trace.push(TraceEvent::Message(message()));
+        }
+    }
+
+    // Records an event with the next token, including whitespace
+    fn trace_event(&self, f: impl FnOnce(TracedToken<L::TokenKind>) -> TraceEvent<L>) {
+        if let Some(trace) = &mut *self.trace.borrow_mut() {
+            trace.push(f(TracedToken::new(self.tokens[0], self.src)));
+        }
+    }
+
+    fn trace_lookahead(&self, expected: impl FnOnce() -> String, matched: bool) {
+        self.trace_event(|token| TraceEvent::Lookahead {
+            expected: expected(),
+            matched,
+            token,
+        });
+    }
+
+    pub fn build(mut self, save_errors: bool) -> Ast<&'s str, L> {
+        self.flush_tokens();
         crate::ast::build_ast(self.events, self.src, save_errors, self.root)
     }
 }
@@ -314,18 +594,22 @@ impl<'t, 's, L: Language> Parser<'t, 's, L> {
 pub struct TokenParser<'p, 't, 's, L: Language>(&'p mut Parser<'t, 's, L>);
 
 impl<'p, 't, 's, L: Language> TokenParser<'p, 't, 's, L> {
+    #[track_caller]
     pub fn eat(&mut self, kind: L::TokenKind) -> bool {
         self.expect(kind).is_some()
     }
 
+    #[track_caller]
     pub fn eat_tokens(&mut self, set: TokenSet<L::TokenKind>) -> bool {
         self.expect_tokens(set).is_some()
     }
 
+    #[track_caller]
     pub fn eat_kw(&mut self, kws: &'static [(&'static str, L::GroupType)]) -> bool {
         self.0.eat_keyword(kws)
     }
 
+    #[track_caller]
     pub fn expect(&mut self, kind: L::TokenKind) -> Option<()> {
         if self.0.at(kind) {
             self.0.bump();
@@ -335,6 +619,7 @@ impl<'p, 't, 's, L: Language> TokenParser<'p, 't, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn expect_tokens(&mut self, set: TokenSet<L::TokenKind>) -> Option<()> {
         if self.0.at_tokens(set) {
             self.0.bump();
@@ -344,6 +629,7 @@ impl<'p, 't, 's, L: Language> TokenParser<'p, 't, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn expect_kw(&mut self, kws: &'static [(&'static str, L::GroupType)]) -> Option<()> {
         if self.0.at_keyword(kws) {
             self.0.bump();
@@ -353,12 +639,27 @@ impl<'p, 't, 's, L: Language> TokenParser<'p, 't, 's, L> {
         }
     }
 
+    #[track_caller]
     pub fn nth(&self, n: usize) -> L::TokenKind {
         assert!(n <= 1);
         self.0.nth(n)
     }
 }
 
+/// The position of the parser the last time a loop checked that it made progress.
+///
+/// See [`Parser::progressed`].
+///
+/// [`Parser::progressed`]: ./struct.Parser.html#method.progressed
+#[derive(Debug, Default)]
+pub struct Progress(Option<usize>);
+
+impl Progress {
+    pub fn new() -> Self {
+        Progress(None)
+    }
+}
+
 #[derive(Debug)]
 pub struct Marker<'t, L: Language>(&'t [Token<L::TokenKind>], usize, bool, DropBomb<&'t str>);
 
@@ -378,35 +679,39 @@ pub struct Lookahead<'p, 't, 's, L: Language> {
 }
 
 impl<'p, 't, 's, L: Language> Lookahead<'p, 't, 's, L> {
+    #[track_caller]
     pub fn at(&mut self, kind: L::TokenKind) -> bool {
-        if self.parser.at(kind) {
-            true
-        } else {
+        let matched = self.parser.is_at(kind);
+        self.trace(|| format!("'{}'", kind), matched);
+        if !matched {
             self.tried.push(kind);
-            false
         }
+        matched
     }
 
+    #[track_caller]
     pub fn at_tks(&self, kind: TokenSet<L::TokenKind>) -> bool {
         self.parser.at_tokens(kind)
     }
 
+    #[track_caller]
     pub fn at_keyword(&mut self, kw: &'static str, gt: L::GroupType) -> bool {
-        if self.parser.at_keyword(&[(kw, gt)]) {
-            true
-        } else {
+        let matched = self.parser.is_at_keyword(&[(kw, gt)]);
+        self.trace(|| format!("'{}'", kw), matched);
+        if !matched {
             self.kw.push((kw, gt));
-            false
         }
+        matched
     }
 
+    #[track_caller]
     pub fn at_keywords(&mut self, ex: &[(&'static str, L::GroupType)]) -> bool {
-        if self.parser.at_keyword(ex) {
-            true
-        } else {
+        let matched = self.parser.is_at_keyword(ex);
+        self.trace(|| keywords(ex), matched);
+        if !matched {
             self.kw.extend_from_slice(ex);
-            false
         }
+        matched
     }
 
     pub fn group_error(&mut self, gt: L::GroupType) {
@@ -415,16 +720,18 @@ impl<'p, 't, 's, L: Language> Lookahead<'p, 't, 's, L> {
 
     pub fn add_errors(self) {
         self.parser
-            .events
-            .push(Event::Error(ExpectedToken::new(self.tried).into()));
+            .push_event(Event::Error(ExpectedToken::new(self.tried).into()));
         self.parser
-            .events
-            .push(Event::Error(ExpectedLit::new(self.kw).into()));
+            .push_event(Event::Error(ExpectedLit::new(self.kw).into()));
         for x in self.groups {
-            self.parser.events.push(Event::Error(ParseError::Group(x)));
+            self.parser.push_event(Event::Error(ParseError::Group(x)));
         }
     }
 
+    fn trace(&self, expected: impl FnOnce() -> String, matched: bool) {
+        self.parser.trace_lookahead(expected, matched);
+    }
+
     pub fn get_errors(self) -> Vec<ParseError<L>> {
         let mut out = vec![
             ExpectedToken::new(self.tried).into(),
@@ -444,3 +751,8 @@ pub enum Event<L: Language> {
     Token(Token<L::TokenKind>),
     Error(ParseError<L>),
 }
+
+fn keywords<G>(ex: &[(&str, G)]) -> String {
+    let kws: Vec<_> = ex.iter().map(|(kw, _)| format!("'{}'", kw)).collect();
+    kws.join(", ")
+}
@@ -48,6 +48,27 @@ pub trait TokenKind: Sized + std::fmt::Debug + std::fmt::Display + Copy + Eq + I
     const START_WHITESPACE: TokenSet<Self>;
 }
 
+/// The kind of a group of tokens in the syntax tree.
+///
+/// The description and example are used to explain parse errors,
+/// so they should make sense after the word "Expected".
+pub trait GroupType: std::fmt::Debug + Copy + Eq {
+    /// Describes the group, for example "an entity selector".
+    fn description(self) -> &'static str;
+
+    /// A short piece of source code that is a valid instance of the group,
+    /// for example `@e[type=pig]`.
+    fn example(self) -> Option<&'static str>;
+
+    /// The name of the group when the tree is dumped, which should not change between runs.
+    ///
+    /// Defaults to the `Debug` form, which has to be overridden for groups that contain data
+    /// such as an index.
+    fn name(self) -> String {
+        format!("{:?}", self)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct TokenSet<T>(u128, PhantomData<*const T>);
 
@@ -73,6 +94,12 @@ impl<T> TokenSet<T> {
     }
 }
 
+impl<T> std::fmt::Debug for TokenSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "TokenSet({:#b})", self.0)
+    }
+}
+
 impl<T: Into<u8>> FromIterator<T> for TokenSet<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut out = 0u128;
@@ -0,0 +1,147 @@
+use crate::{parser::Language, ParseError, Span, Token, TokenKind};
+
+use std::fmt::{self, Display, Formatter};
+
+/// A token as it was seen by the parser when an event was traced.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TracedToken<K: TokenKind> {
+    kind: K,
+    text: String,
+    span: Span,
+}
+
+impl<K: TokenKind> TracedToken<K> {
+    pub(crate) fn new(tk: Token<K>, src: &str) -> Self {
+        TracedToken {
+            kind: tk.kind(),
+            text: tk.string(src).to_owned(),
+            span: tk.span(),
+        }
+    }
+
+    pub fn kind(&self) -> K {
+        self.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl<K: TokenKind> Display for TracedToken<K> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?} {:?} {}", self.kind, self.text, self.span.start())
+    }
+}
+
+/// A decision made by the [`Parser`] while tracing is enabled.
+///
+/// Every event records the token the parser was looking at,
+/// which is the next token including whitespace.
+///
+/// [`Parser`]: ./parser/struct.Parser.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TraceEvent<L: Language> {
+    /// A group was started.
+    Start {
+        kind: L::GroupType,
+        token: TracedToken<L::TokenKind>,
+    },
+    /// The innermost group that was started was finished.
+    Finish { token: TracedToken<L::TokenKind> },
+    /// The innermost group that was started was cancelled,
+    /// and everything that was parsed since was thrown away.
+    Cancel { token: TracedToken<L::TokenKind> },
+    /// A token was added to the current group.
+    Bump { token: TracedToken<L::TokenKind> },
+    /// The grammar checked for a token or keyword, either directly
+    /// or through a [`Lookahead`].
+    ///
+    /// [`Lookahead`]: ./parser/struct.Lookahead.html
+    Lookahead {
+        expected: String,
+        matched: bool,
+        token: TracedToken<L::TokenKind>,
+    },
+    /// An error was added to the current group.
+    Error {
+        error: ParseError<L>,
+        token: TracedToken<L::TokenKind>,
+    },
+    /// A message from the grammar, such as why it picked a branch.
+    Message(String),
+}
+
+/// The events recorded by a [`Parser`] with tracing enabled, in order.
+///
+/// The trace is displayed with one event on each line,
+/// indented by the number of groups that are open:
+///
+/// ```text
+/// start Command at Word "say" 0:0
+///   bump Word "say" 0:0
+/// finish at Eof "" 0:3
+/// ```
+///
+/// [`Parser`]: ./parser/struct.Parser.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Trace<L: Language>(Vec<TraceEvent<L>>);
+
+impl<L: Language> Trace<L> {
+    pub(crate) fn new(events: Vec<TraceEvent<L>>) -> Self {
+        Trace(events)
+    }
+
+    pub fn events(&self) -> &[TraceEvent<L>] {
+        &self.0
+    }
+
+    /// Gets the messages that the grammar added, in order.
+    pub fn messages(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|evt| match evt {
+            TraceEvent::Message(msg) => Some(msg.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl<L: Language> Display for Trace<L> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut depth = 0usize;
+        for evt in &self.0 {
+            if let TraceEvent::Finish { .. } | TraceEvent::Cancel { .. } = evt {
+                depth = depth.saturating_sub(1);
+            }
+            write!(f, "{}", "  ".repeat(depth))?;
+            match evt {
+                TraceEvent::Start { kind, token } => {
+                    depth += 1;
+                    writeln!(f, "start {:?} at {}", kind, token)
+                }
+                TraceEvent::Finish { token } => writeln!(f, "finish at {}", token),
+                TraceEvent::Cancel { token } => writeln!(f, "cancel at {}", token),
+                TraceEvent::Bump { token } => writeln!(f, "bump {}", token),
+                TraceEvent::Lookahead {
+                    expected,
+                    matched,
+                    token,
+                } => writeln!(
+                    f,
+                    "lookahead {} {} at {}",
+                    expected,
+                    if *matched { "matched" } else { "failed" },
+                    token
+                ),
+                TraceEvent::Error { error, token } => {
+                    writeln!(f, "error {:?} at {}", error.to_string(), token)
+                }
+                TraceEvent::Message(msg) => writeln!(f, "# {}", msg),
+            }?;
+        }
+        Ok(())
+    }
+}
@@ -22,6 +22,14 @@ impl<I: ArenaId, T> Arena<I, T> {
         I::from(RawId(ind))
     }
 
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn indices(&self) -> impl Iterator<Item = I> {
         (0..self.0.len()).map(|ind| I::from(RawId(ind)))
     }
@@ -22,6 +22,191 @@ impl Commands {
     pub fn root_index(&self) -> Index {
         self.root
     }
+
+    /// Finds a node by the names of the nodes leading to it, such as `["execute", "if", "score"]`.
+    ///
+    /// Redirects are followed, so `["execute", "as", "targets", "at"]` finds the `at` under `execute`.
+    pub fn find_path(&self, path: &[&str]) -> Option<Index> {
+        let mut ind = self.root;
+        for name in path {
+            ind = self
+                .next_nodes(ind)
+                .find(|(_, c)| c.name() == *name)
+                .map(|(i, _)| i)?;
+        }
+        Some(ind)
+    }
+
+    /// The nodes from the root to a node, including both.
+    pub fn path(&self, index: Index) -> Vec<Index> {
+        let mut path = vec![index];
+        while let Some(parent) = self[*path.last().unwrap()].parent {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
+
+    /// The path to every node that can be executed, in the order of the tree.
+    ///
+    /// Redirects aren't followed, so every path is part of the tree and ends at a different node.
+    pub fn executable_paths(&self) -> impl Iterator<Item = Vec<Index>> + '_ {
+        let mut stack = vec![vec![self.root]];
+        std::iter::from_fn(move || {
+            while let Some(path) = stack.pop() {
+                let node = &self[*path.last().unwrap()];
+                for child in node.children.iter().rev() {
+                    let mut child_path = path.clone();
+                    child_path.push(*child);
+                    stack.push(child_path);
+                }
+                if node.executable {
+                    return Some(path);
+                }
+            }
+            None
+        })
+    }
+
+    /// The nodes that can come after a node, which are the children of the node it redirects to if there is one.
+    pub fn next_nodes(&self, index: Index) -> impl Iterator<Item = (Index, &Command)> {
+        let mut node = &self[index];
+        let mut seen = vec![index];
+        while let Some(target) = node.redirect {
+            // A malformed tree can redirect in a circle, which leads nowhere
+            if seen.contains(&target) {
+                break;
+            }
+            seen.push(target);
+            node = &self[target];
+        }
+        node.children(self)
+    }
+
+    /// The types of the arguments that can come after a node, without repeats.
+    pub fn next_parser_types(&self, index: Index) -> Vec<ParserType> {
+        let mut out: Vec<ParserType> = vec![];
+        for (_, node) in self.next_nodes(index) {
+            if let CommandNodeType::Argument { parser_type } = node.node_type {
+                if !out.contains(&parser_type) {
+                    out.push(parser_type);
+                }
+            }
+        }
+        out
+    }
+
+    /// The usage of every command that can be run from a node, the same as Brigadier's `getAllUsage`.
+    ///
+    /// Each line starts after `index`, so the usage of the root is a list of every command.
+    pub fn all_usage(&self, index: Index) -> Vec<String> {
+        let mut out = vec![];
+        self.all_usage_from(index, String::new(), &mut out);
+        out
+    }
+
+    fn all_usage_from(&self, index: Index, prefix: String, out: &mut Vec<String>) {
+        let node = &self[index];
+        if node.executable {
+            out.push(prefix.clone());
+        }
+        if let Some(target) = node.redirect {
+            let redirect = self.redirect_usage(target);
+            if prefix.is_empty() {
+                out.push(format!("{} {}", node.usage_text(), redirect));
+            } else {
+                out.push(format!("{} {}", prefix, redirect));
+            }
+        } else {
+            for (child_ind, child) in node.children(self) {
+                let prefix = if prefix.is_empty() {
+                    child.usage_text()
+                } else {
+                    format!("{} {}", prefix, child.usage_text())
+                };
+                self.all_usage_from(child_ind, prefix, out);
+            }
+        }
+    }
+
+    /// A short usage for each child of a node, the same as Brigadier's `getSmartUsage`.
+    ///
+    /// Optional arguments are put in `[]` and alternatives in `(a|b)`,
+    /// such as `give <targets> <item> [<count>]`.
+    pub fn smart_usage(&self, index: Index) -> Vec<(Index, String)> {
+        self[index]
+            .children(self)
+            .map(|(ind, _)| (ind, self.smart_usage_of(ind, false, false)))
+            .collect()
+    }
+
+    fn smart_usage_of(&self, index: Index, optional: bool, deep: bool) -> String {
+        let node = &self[index];
+        let this = if optional {
+            format!("[{}]", node.usage_text())
+        } else {
+            node.usage_text()
+        };
+        if deep {
+            return this;
+        }
+        if let Some(target) = node.redirect {
+            return format!("{} {}", this, self.redirect_usage(target));
+        }
+        // Whatever follows an executable node can be left out
+        let child_optional = node.executable;
+        match node.children.as_slice() {
+            [] => this,
+            [child] => format!(
+                "{} {}",
+                this,
+                self.smart_usage_of(*child, child_optional, child_optional)
+            ),
+            children => {
+                let mut usages: Vec<String> = vec![];
+                for child in children {
+                    let usage = self.smart_usage_of(*child, child_optional, true);
+                    if !usages.contains(&usage) {
+                        usages.push(usage);
+                    }
+                }
+                if usages.len() == 1 {
+                    if child_optional {
+                        format!("{} [{}]", this, usages[0])
+                    } else {
+                        format!("{} {}", this, usages[0])
+                    }
+                } else {
+                    let (open, close) = if child_optional {
+                        ("[", "]")
+                    } else {
+                        ("(", ")")
+                    };
+                    let alternatives = children
+                        .iter()
+                        .map(|c| self[*c].usage_text())
+                        .collect::<Vec<_>>();
+                    format!("{} {}{}{}", this, open, alternatives.join("|"), close)
+                }
+            }
+        }
+    }
+
+    fn redirect_usage(&self, target: Index) -> String {
+        if target == self.root {
+            "...".to_owned()
+        } else {
+            format!("-> {}", self[target].usage_text())
+        }
+    }
+
+    /// The lines that `/help` prints, such as `/give <targets> <item> [<count>]`.
+    pub fn help(&self) -> Vec<String> {
+        self.smart_usage(self.root)
+            .into_iter()
+            .map(|(_, usage)| format!("/{}", usage))
+            .collect()
+    }
 }
 
 impl ops::Index<Index> for Commands {
@@ -45,6 +230,10 @@ impl From<Index> for usize {
 pub struct Command {
     name: String,
     children: Vec<Index>,
+    #[serde(default)]
+    redirect: Option<Index>,
+    #[serde(default)]
+    parent: Option<Index>,
     executable: bool,
     node_type: CommandNodeType,
 }
@@ -65,6 +254,19 @@ impl Command {
         &self.children
     }
 
+    /// The node whose children can follow this one, such as `execute` after `execute as <targets>`,
+    /// or `teleport` for the alias `tp`.
+    ///
+    /// A node that redirects has no children of its own.
+    pub fn redirect(&self) -> Option<Index> {
+        self.redirect
+    }
+
+    /// The node that this one is a child of, which is `None` for the root.
+    pub fn parent(&self) -> Option<Index> {
+        self.parent
+    }
+
     pub fn executable(&self) -> bool {
         self.executable
     }
@@ -72,6 +274,15 @@ impl Command {
     pub fn node_type(&self) -> CommandNodeType {
         self.node_type
     }
+
+    /// How this node is written in usage text, such as `give` for a literal or `<targets>` for an argument.
+    pub fn usage_text(&self) -> String {
+        match self.node_type {
+            CommandNodeType::Root => String::new(),
+            CommandNodeType::Literal => self.name.clone(),
+            CommandNodeType::Argument { .. } => format!("<{}>", self.name),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
@@ -219,6 +430,27 @@ pub struct Range<T> {
     max: Option<T>,
 }
 
+impl<T: Copy + PartialOrd> Range<T> {
+    pub fn new(min: Option<T>, max: Option<T>) -> Self {
+        Range { min, max }
+    }
+
+    /// Gets the smallest allowed value, if there is one.
+    pub fn min(&self) -> Option<T> {
+        self.min
+    }
+
+    /// Gets the largest allowed value, if there is one.
+    pub fn max(&self) -> Option<T> {
+        self.max
+    }
+
+    /// Checks whether the value is in the range. Both bounds are inclusive.
+    pub fn contains(&self, value: T) -> bool {
+        self.min.is_none_or(|min| min <= value) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 pub enum EntityAmount {
     #[serde(rename = "single")]
@@ -256,7 +488,7 @@ impl CommandsBuilder {
         let mut out = CommandsBuilder {
             arena: Arena::new(),
         };
-        let rootind = out.register_command(String::new(), root, &mut tree);
+        let rootind = out.register_command(String::new(), root, None, &mut tree);
         let tree = tree.get("").unwrap();
         out.resolve_command(&tree, &tree, rootind);
         (out.arena, rootind)
@@ -266,19 +498,29 @@ impl CommandsBuilder {
         &mut self,
         name: String,
         node: CommandNode,
+        parent: Option<Index>,
         tree: &mut HashMap<String, CommandTree>,
     ) -> Index {
         let ind = self.arena.push(Command {
             name: name.clone(),
             children: Vec::new(),
+            redirect: None,
+            parent,
             executable: node.executable,
             node_type: node.node_type,
         });
         if let Some(map) = node.children {
             let mut inds: Vec<Index> = vec![];
             let mut tree_branch: HashMap<String, CommandTree> = HashMap::new();
+            // Brigadier keeps literals before arguments and sorts each of them by name,
+            // which is the order of its usage text
+            let mut map = map.into_iter().collect::<Vec<_>>();
+            map.sort_by_key(|(name, child)| {
+                let literal = matches!(child.node_type, CommandNodeType::Literal);
+                (!literal, name.clone())
+            });
             for (name, child) in map {
-                inds.push(self.register_command(name, child, &mut tree_branch))
+                inds.push(self.register_command(name, child, Some(ind), &mut tree_branch))
             }
             self.arena[ind].children = inds;
             tree.insert(name, CommandTree(TreeBranch::Children(tree_branch), ind));
@@ -303,7 +545,7 @@ impl CommandsBuilder {
                 }
             }
             TreeBranch::Root => {
-                self.arena[tree.1].children = vec![root_ind];
+                self.arena[tree.1].redirect = Some(root_ind);
             }
             TreeBranch::Redirect(path) => {
                 let mut top = root;
@@ -320,7 +562,7 @@ impl CommandsBuilder {
                         }
                     }
                 }
-                self.arena[tree.1].children = self.arena[top.1].children.clone();
+                self.arena[tree.1].redirect = Some(top.1);
             }
         }
     }
@@ -4,6 +4,7 @@ pub mod arena;
 pub mod commands;
 mod dropbomb;
 mod identifier;
+pub mod suggest;
 
 pub use dropbomb::DropBomb;
 pub use identifier::Identifier;
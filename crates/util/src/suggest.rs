@@ -0,0 +1,101 @@
+//! Suggestions for misspelled names, like the "did you mean" hints of a compiler.
+
+/// The optimal string alignment distance between two strings,
+/// which is the Levenshtein distance where swapping two adjacent characters counts as one edit.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Three rows are enough, since a transposition only looks two rows back
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Finds the candidate closest to `name`, if any is close enough to be a likely typo.
+///
+/// A third of the length of `name` may be edited, but always at least one character.
+/// Ties are broken by picking the candidate that sorts first, so the result doesn't
+/// depend on the order of `candidates`.
+pub fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(name, c), c))
+        .filter(|&(dist, _)| dist <= max)
+        .min()
+        .map(|(_, c)| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{did_you_mean, edit_distance};
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("stone", "stone"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        // A swap of two neighbours is a single edit
+        assert_eq!(edit_distance("stnoe", "stone"), 1);
+        assert_eq!(edit_distance("ab", "ba"), 1);
+        // But a swapped pair can't be edited again
+        assert_eq!(edit_distance("ca", "abc"), 3);
+    }
+
+    #[test]
+    fn distance_counts_characters() {
+        assert_eq!(edit_distance("é", "e"), 1);
+        assert_eq!(edit_distance("naïve", "naive"), 1);
+        assert_eq!(edit_distance("日本語", "日語本"), 1);
+        assert_eq!(edit_distance("🐷", "🐮"), 1);
+    }
+
+    #[test]
+    fn suggestions() {
+        let names = ["speed", "slowness", "haste"];
+        assert_eq!(did_you_mean("sped", names.iter().copied()), Some("speed"));
+        assert_eq!(
+            did_you_mean("slownes", names.iter().copied()),
+            Some("slowness")
+        );
+        assert_eq!(did_you_mean("nausea", names.iter().copied()), None);
+        // Short names may still have one typo
+        assert_eq!(did_you_mean("a", ["b"].iter().copied()), Some("b"));
+        assert_eq!(did_you_mean("ab", ["cd"].iter().copied()), None);
+    }
+
+    #[test]
+    fn suggestion_ties() {
+        assert_eq!(
+            did_you_mean("cat", ["hat", "bat"].iter().copied()),
+            Some("bat")
+        );
+        assert_eq!(
+            did_you_mean("cat", ["bat", "hat"].iter().copied()),
+            Some("bat")
+        );
+        // A closer candidate wins over one that sorts first
+        assert_eq!(edit_distance("slownes", "lowness"), 2);
+        assert_eq!(
+            did_you_mean("slownes", ["lowness", "slowness"].iter().copied()),
+            Some("slowness")
+        );
+    }
+}